@@ -0,0 +1,190 @@
+// Checks that generated examples define their tasks unambiguously
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+use crate::dataset::matches_filter;
+use crate::field::{check_colors, invert_permutation, with_colors};
+use crate::grid::Grid;
+use crate::registry::{registry_with, Augmentation};
+use crate::tasks::{task_permute_colors, Example};
+use crate::visualization::{read_tasks, test_pair};
+use crate::{Error, Result};
+
+/// Two examples with the same input and different outputs, so the output can't be derived from the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub input: Grid,
+    pub outputs: [Grid; 2],
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [first, second] = &self.outputs;
+        write!(f, "input {:?} has outputs {:?} and {:?}", &self.input[..], &first[..], &second[..])
+    }
+}
+
+/// Finds the first input that has two different outputs, in order of `examples`.
+pub fn find_conflict(examples: &[Example]) -> Option<Conflict> {
+    let mut outputs: HashMap<&Grid, &Grid> = HashMap::new();
+    for example in examples {
+        match outputs.get(&example.input) {
+            Some(&output) if output != &example.output => {
+                return Some(Conflict { input: example.input.clone(), outputs: [output.clone(), example.output.clone()] });
+            }
+            Some(_) => {}
+            None => {
+                outputs.insert(&example.input, &example.output);
+            }
+        }
+    }
+    None
+}
+
+/// Two tasks which have the same output for some inputs and different outputs for others, so they can't be told apart from examples of the first kind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Overlap {
+    pub tasks: [String; 2],
+    /// Number of inputs with the same output in both tasks.
+    pub same: usize,
+    /// Number of inputs with different outputs.
+    pub different: usize,
+    /// First input with different outputs, outputs are in the order of `tasks`.
+    pub conflict: Conflict,
+}
+
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [first, second] = &self.tasks;
+        write!(f, "tasks `{first}` and `{second}` agree on {} inputs and differ on {}, for example {}", self.same, self.different, self.conflict)
+    }
+}
+
+/// Finds pairs of tasks with overlapping examples, see [`Overlap`]. Tasks which never agree are just different tasks, so they are not reported.
+pub fn find_overlaps(tasks: &[(String, Vec<Example>)]) -> Vec<Overlap> {
+    let mut outputs: HashMap<&Grid, Vec<(usize, &Grid)>> = HashMap::new();
+    for (task, (_, examples)) in tasks.iter().enumerate() {
+        for example in examples {
+            let seen = outputs.entry(&example.input).or_default();
+            // Conflicts within a task are found by `find_conflict`
+            if seen.last().is_none_or(|&(last, _)| last != task) {
+                seen.push((task, &example.output));
+            }
+        }
+    }
+
+    // Inputs are visited in order of examples, so the reported conflicts don't depend on hashing
+    let mut pairs: BTreeMap<(usize, usize), (usize, usize, Option<Conflict>)> = BTreeMap::new();
+    for (_, examples) in tasks {
+        for example in examples {
+            let Some(seen) = outputs.remove(&example.input) else {
+                continue;
+            };
+            for (i, &(first, first_output)) in seen.iter().enumerate() {
+                for &(second, second_output) in &seen[i + 1..] {
+                    let (same, different, conflict) = pairs.entry((first, second)).or_default();
+                    if first_output == second_output {
+                        *same += 1;
+                    } else {
+                        *different += 1;
+                        conflict.get_or_insert_with(|| Conflict {
+                            input: example.input.clone(),
+                            outputs: [first_output.clone(), second_output.clone()],
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    pairs.into_iter()
+        .filter_map(|((first, second), (same, different, conflict))| match conflict {
+            Some(conflict) if same > 0 => Some(Overlap {
+                tasks: [tasks[first].0.clone(), tasks[second].0.clone()],
+                same,
+                different,
+                conflict,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Problems found by [`check_dir`].
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// Tasks with an input that has two different outputs.
+    pub conflicts: Vec<(String, Conflict)>,
+    pub overlaps: Vec<Overlap>,
+    /// Tasks with an example which is not explained by the reference solver, see [`crate::registry::TaskVariant::verify`], and the first such example.
+    pub unsolved: Vec<(String, Example)>,
+}
+
+/// Checks task files in `tasks_dir` whose names contain `filter`, examples are test pairs of all episodes.
+///
+/// Every example is also checked by the reference solver of its task, after the color permutation of its episode is undone. Tasks were generated with `colors` colors.
+pub fn check_dir(tasks_dir: &Path, filter: Option<&str>, colors: u8) -> Result<Report> {
+    check_colors(colors)?;
+    with_colors(colors, || {
+        let registry = registry_with(&Augmentation::ALL);
+        let mut tasks = vec![];
+        let mut unsolved = vec![];
+        for variant in registry.variants() {
+            let path = tasks_dir.join(format!("{}.json", variant.name));
+            if !matches_filter(&variant.name, filter) || !path.exists() {
+                continue;
+            }
+            let mut examples = vec![];
+            for task in read_tasks(&path)? {
+                let (input, output) = test_pair(&task).expect("pairs are checked by `read_tasks`");
+                let example = Example { input: input.clone(), output: output.clone() };
+                // Solvers work with colors of the original task
+                let original = match task.metadata.as_ref().and_then(|metadata| metadata.permutation.as_ref()) {
+                    Some(permutation) => {
+                        let inverse = invert_permutation(permutation)
+                            .ok_or_else(|| Error::Task { task: path.display().to_string(), source: Box::new(Error::Format(format!("invalid permutation {permutation:?}"))) })?;
+                        task_permute_colors(Some(example.clone()), &inverse).expect("permutation keeps the example")
+                    }
+                    None => example.clone(),
+                };
+                if !variant.verify(&original) && !unsolved.iter().any(|(name, _)| name == &variant.name) {
+                    unsolved.push((variant.name.clone(), example.clone()));
+                }
+                examples.push(example);
+            }
+            tasks.push((variant.name.clone(), examples));
+        }
+
+        let conflicts = tasks.iter()
+            .filter_map(|(name, examples)| Some((name.clone(), find_conflict(examples)?)))
+            .collect();
+        Ok(Report { conflicts, overlaps: find_overlaps(&tasks), unsolved })
+    })
+}
+
+/// What to do when a generated task turns out to be ambiguous, see [`find_conflict`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AmbiguityPolicy {
+    /// Print a warning and save the task anyway.
+    Warn,
+    /// Print a message and don't save the task.
+    #[default]
+    Skip,
+    /// Fail the whole generation.
+    Error,
+}
+
+impl std::str::FromStr for AmbiguityPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "warn" => Ok(AmbiguityPolicy::Warn),
+            "skip" => Ok(AmbiguityPolicy::Skip),
+            "error" => Ok(AmbiguityPolicy::Error),
+            _ => Err(format!("unknown policy `{s}`, expected `warn`, `skip` or `error`")),
+        }
+    }
+}
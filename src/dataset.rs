@@ -0,0 +1,495 @@
+// Generation of examples and saving them in ARC format
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+use crate::checks::{find_conflict, AmbiguityPolicy};
+use crate::episode::{choose_train, episode_seed, EpisodeMetadata};
+use crate::field::{check_colors, random_permutation, with_colors, COLORS};
+use crate::grid::Grid;
+use crate::layout::LayoutStats;
+use crate::registry::{registry_with, Augmentation, Sampling, TaskVariant};
+use crate::splits::Holdout;
+use crate::tasks::{task_permute_colors, Example};
+use crate::{Error, Result};
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq, Default)]
+pub struct Example2D {
+    pub input: Vec<Grid>,
+    pub output: Vec<Grid>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq, Default)]
+pub struct ArcTask2D {
+    pub train: Vec<Example2D>,
+    pub test: Vec<Example2D>,
+    /// Present only for episodes with train pairs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<EpisodeMetadata>,
+}
+
+impl From<Example> for Example2D {
+    fn from(x: Example) -> Self {
+        Example2D {
+            input: vec![x.input],
+            output: vec![x.output],
+        }
+    }
+}
+
+pub fn save_json_to_file<T: Serialize>(t: &T, path: &Path) -> Result<()> {
+    let mut file = fs::File::create(path)?;
+    let json = serde_json::to_string(&t)?;
+    write!(file, "{}", json)?;
+    Ok(())
+}
+
+/// Inclusive range of field sizes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct SizeRange {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl SizeRange {
+    pub fn fixed(size: usize) -> Self {
+        SizeRange { min: size, max: size }
+    }
+
+    pub fn sample(&self, rng: &mut StdRng) -> usize {
+        rng.gen_range(self.min..=self.max)
+    }
+
+    /// Range without sizes smaller than `min_size`, `None` if nothing is left.
+    pub fn at_least(&self, min_size: usize) -> Option<SizeRange> {
+        let range = SizeRange { min: self.min.max(min_size), max: self.max };
+        (range.min <= range.max).then_some(range)
+    }
+}
+
+impl Default for SizeRange {
+    fn default() -> Self {
+        SizeRange { min: 5, max: 29 }
+    }
+}
+
+impl std::str::FromStr for SizeRange {
+    type Err = String;
+
+    /// Parses `12`, `5..30` or `5..=29`.
+    fn from_str(s: &str) -> Result<Self, String> {
+        let parse = |s: &str| s.trim().parse::<usize>().map_err(|err| format!("invalid size `{s}`: {err}"));
+        let range = if let Some((min, max)) = s.split_once("..=") {
+            SizeRange { min: parse(min)?, max: parse(max)? }
+        } else if let Some((min, max)) = s.split_once("..") {
+            let max = parse(max)?;
+            if max == 0 {
+                return Err(format!("empty size range `{s}`"));
+            }
+            SizeRange { min: parse(min)?, max: max - 1 }
+        } else {
+            SizeRange::fixed(parse(s)?)
+        };
+        if range.min == 0 || range.min > range.max {
+            return Err(format!("empty size range `{s}`"));
+        }
+        Ok(range)
+    }
+}
+
+/// Settings of `generate_all`.
+#[derive(Clone, Debug)]
+pub struct GenerateConfig {
+    /// Directory where json file of each task is written.
+    pub output_dir: PathBuf,
+    pub seed: u64,
+    /// Maximum number of examples in each task.
+    pub examples: usize,
+    /// Generate only tasks whose name contains this string.
+    pub filter: Option<String>,
+    pub sizes: SizeRange,
+    /// Sizes for tasks whose name contains the string, they override `sizes`, last matching one wins.
+    pub task_sizes: Vec<(String, SizeRange)>,
+    pub underfill: UnderfillPolicy,
+    /// Number of threads, 0 means number of CPUs.
+    pub jobs: usize,
+    /// Number of train pairs in each episode.
+    pub train_pairs: usize,
+    /// Number of colors, not counting the background. Tasks with fixed colors outside of it are skipped.
+    pub colors: u8,
+    /// Symmetries of tasks which are added as new tasks, see [`crate::registry::TaskDef::symmetries`].
+    pub augmentations: Vec<Augmentation>,
+    /// What to do with inverse tasks whose original task maps different inputs to the same output.
+    pub non_injective: AmbiguityPolicy,
+    /// What to do with other tasks which have an input with two different outputs.
+    pub ambiguous: AmbiguityPolicy,
+    pub sampling: Sampling,
+    /// Save every example of tasks that can be enumerated instead of sampling, `examples` is the maximum, see [`enumerate_task`].
+    pub enumerate: bool,
+    /// Attributes of examples which are held out, see [`Holdout`].
+    pub holdouts: Vec<Holdout>,
+    /// Generate only examples with held out attributes, instead of only examples without them.
+    pub held_out: bool,
+}
+
+impl GenerateConfig {
+    /// Sizes that are sampled for the task, `None` if all of them are too small for it.
+    pub fn sizes_for(&self, variant: &TaskVariant) -> Option<SizeRange> {
+        sizes_for(self.sizes, &self.task_sizes, variant)
+    }
+
+    /// The example belongs to the dataset: it has a held out attribute if and only if `held_out` is set.
+    pub fn keeps(&self, variant: &TaskVariant, example: &Example) -> bool {
+        if self.holdouts.is_empty() {
+            return true;
+        }
+        let attributes = variant.attributes(example);
+        self.holdouts.iter().any(|holdout| holdout.matches(&attributes)) == self.held_out
+    }
+}
+
+/// Sizes from `task_sizes` whose name matches the task, or `sizes`, without sizes that are too small for the task.
+pub fn sizes_for(sizes: SizeRange, task_sizes: &[(String, SizeRange)], variant: &TaskVariant) -> Option<SizeRange> {
+    let sizes = task_sizes.iter()
+        .rev()
+        .find(|(name, _)| variant.name.contains(name.as_str()))
+        .map_or(sizes, |(_, sizes)| *sizes);
+    sizes.at_least(variant.min_size)
+}
+
+impl Default for GenerateConfig {
+    fn default() -> Self {
+        GenerateConfig {
+            output_dir: PathBuf::from("tasks"),
+            seed: 42,
+            examples: 1000,
+            filter: None,
+            sizes: SizeRange::default(),
+            task_sizes: vec![],
+            underfill: UnderfillPolicy::default(),
+            jobs: 0,
+            train_pairs: 0,
+            colors: COLORS,
+            augmentations: vec![],
+            non_injective: AmbiguityPolicy::default(),
+            ambiguous: AmbiguityPolicy::Warn,
+            sampling: Sampling::default(),
+            enumerate: false,
+            holdouts: vec![],
+            held_out: false,
+        }
+    }
+}
+
+/// FNV-1a hash of all parts, it's used instead of std hashers because they are not guaranteed to be stable between Rust versions.
+pub fn stable_hash(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for part in parts {
+        for byte in *part {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+/// Seed of the task, it depends only on the global seed and the task itself, so adding or removing other tasks doesn't change it.
+pub fn task_seed(seed: u64, variant: &TaskVariant) -> u64 {
+    let seed = seed.to_le_bytes();
+    let mut parts: Vec<&[u8]> = vec![&seed, variant.name.as_bytes()];
+    let values = variant.params.0.iter().map(|(_, value)| (*value as u64).to_le_bytes()).collect::<Vec<_>>();
+    for ((key, _), value) in variant.params.0.iter().zip(&values) {
+        parts.push(key.as_bytes());
+        parts.push(value);
+    }
+    stable_hash(&parts)
+}
+
+/// What to do when a task has fewer unique examples than requested.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnderfillPolicy {
+    /// Make `2 * count` attempts and print a warning if that's not enough.
+    #[default]
+    Warn,
+    /// Make `2 * count` attempts and fail if that's not enough.
+    Error,
+    /// Sample until there are `count` examples, or until new examples stop appearing.
+    Adaptive,
+}
+
+impl std::str::FromStr for UnderfillPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "warn" => Ok(UnderfillPolicy::Warn),
+            "error" => Ok(UnderfillPolicy::Error),
+            "adaptive" => Ok(UnderfillPolicy::Adaptive),
+            _ => Err(format!("unknown policy `{s}`, expected `warn`, `error` or `adaptive`")),
+        }
+    }
+}
+
+/// In adaptive mode, sampling stops after this many attempts per requested example, but at least per 100 examples, without a new example.
+///
+/// It's a heuristic: the task may still have examples which were not sampled, only enumeration proves there are none.
+const ADAPTIVE_PATIENCE: usize = 10;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GenerationStats {
+    pub attempts: usize,
+    /// Attempts where the task returned `None`.
+    pub rejected: usize,
+    /// Attempts that generated an already existing example.
+    pub duplicates: usize,
+    /// Number of unique examples.
+    pub count: usize,
+    /// Adaptive sampling stopped because new examples stopped appearing, see [`UnderfillPolicy::Adaptive`].
+    pub stalled: bool,
+    /// Examples were enumerated, so the task has no other examples of these sizes, see [`enumerate_task`].
+    pub complete: bool,
+    /// Layouts of inputs of the unique examples.
+    pub layouts: LayoutStats,
+}
+
+impl std::fmt::Display for GenerationStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} examples from {} attempts ({} rejected, {} duplicates)", self.count, self.attempts, self.rejected, self.duplicates)?;
+        if self.stalled {
+            write!(f, ", stalled")?;
+        }
+        if self.complete {
+            write!(f, ", complete")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn generate_task<F: FnMut(usize, &mut StdRng) -> Option<Example>>(rng: &mut StdRng, count: usize, sizes: SizeRange, policy: UnderfillPolicy, mut f: F) -> (Vec<Example>, GenerationStats) {
+    // Examples are kept in order of generation, so result is the same for the same seed
+    let mut seen = HashSet::new();
+    let mut examples = vec![];
+    let mut stats = GenerationStats::default();
+    let patience = count.max(100) * ADAPTIVE_PATIENCE;
+    let mut attempts_without_new = 0;
+    while examples.len() < count {
+        if policy == UnderfillPolicy::Adaptive {
+            if attempts_without_new >= patience {
+                stats.stalled = true;
+                break;
+            }
+        } else if stats.attempts >= count * 2 {
+            break;
+        }
+
+        stats.attempts += 1;
+        attempts_without_new += 1;
+        let size = sizes.sample(rng);
+        match f(size, rng) {
+            None => stats.rejected += 1,
+            Some(task) => {
+                if seen.insert(task.clone()) {
+                    examples.push(task);
+                    attempts_without_new = 0;
+                } else {
+                    stats.duplicates += 1;
+                }
+            }
+        }
+    }
+    stats.count = examples.len();
+    stats.layouts = LayoutStats::of(&examples);
+    examples.shuffle(rng);
+    (examples, stats)
+}
+
+/// Every example of the task for every size of `sizes`, in order of sizes, see [`TaskVariant::enumerate`].
+///
+/// `None` if the task can't be enumerated or it has more than `limit` unique examples.
+///
+/// Examples which are not kept by `keep` are counted as rejected.
+pub fn enumerate_task(variant: &TaskVariant, sizes: SizeRange, limit: usize, keep: impl Fn(&Example) -> bool) -> Option<(Vec<Example>, GenerationStats)> {
+    let mut seen = HashSet::new();
+    let mut examples = vec![];
+    let mut stats = GenerationStats::default();
+    for size in sizes.min..=sizes.max {
+        for example in variant.enumerate(size)? {
+            stats.attempts += 1;
+            if !keep(&example) {
+                stats.rejected += 1;
+            } else if !seen.insert(example.clone()) {
+                stats.duplicates += 1;
+            } else if examples.len() == limit {
+                return None;
+            } else {
+                examples.push(example);
+            }
+        }
+    }
+    stats.count = examples.len();
+    stats.complete = true;
+    stats.layouts = LayoutStats::of(&examples);
+    Some((examples, stats))
+}
+
+/// Converts examples to ARC tasks, each example becomes test of its own task with `train_pairs` other examples as train, see [`choose_train`].
+///
+/// Branches and color permutation of episodes are taken from `variant`, if it's given. The permutation keeps fixed colors of the task and `held_out_colors` in place, so a held out color appears in an episode only if it's in its examples.
+pub fn to_arc_tasks(examples: &[Example], train_pairs: usize, variant: Option<&TaskVariant>, held_out_colors: &[u8], seed: u64) -> Result<Vec<ArcTask2D>> {
+    let branches = variant.and_then(|variant| variant.branches.as_ref());
+    let mut tasks = vec![];
+    for (i, example) in examples.iter().enumerate() {
+        let seed = episode_seed(seed, i);
+        let (train, mut metadata) = choose_train(examples, i, train_pairs, branches, seed)?;
+        // Separate generator, so permutation doesn't change the choice of train pairs
+        let permutation = variant.filter(|variant| variant.permutes_colors()).map(|variant| {
+            let fixed = variant.fixed_colors.iter().chain(held_out_colors).copied().collect::<Vec<_>>();
+            random_permutation(&fixed, &mut StdRng::seed_from_u64(stable_hash(&[&seed.to_le_bytes(), b"colors"])))
+        });
+        let convert = |example: &Example| -> Example2D {
+            match &permutation {
+                Some(permutation) => task_permute_colors(Some(example.clone()), permutation).expect("permutation keeps the example").into(),
+                None => example.clone().into(),
+            }
+        };
+        let has_metadata = train_pairs > 0 || permutation.is_some();
+        metadata.permutation = permutation.clone();
+        tasks.push(ArcTask2D {
+            train: train.into_iter().map(|j| convert(&examples[j])).collect(),
+            test: vec![convert(example)],
+            metadata: has_metadata.then_some(metadata),
+        });
+    }
+    Ok(tasks)
+}
+
+pub fn save_task(dir: &Path, name: &str, examples: &[Example], train_pairs: usize, variant: Option<&TaskVariant>, held_out_colors: &[u8], seed: u64) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    save_json_to_file(&to_arc_tasks(examples, train_pairs, variant, held_out_colors, seed)?, &dir.join(format!("{name}.json")))
+}
+
+/// Result of [`generate_and_save`] for one task.
+enum Outcome {
+    /// Task was saved, the warning about its ambiguity is printed.
+    Saved { stats: GenerationStats, warning: Option<String> },
+    /// Task was not saved, the reason is printed.
+    Skipped(String),
+}
+
+/// Generates and saves one task, unless it doesn't fit the size or palette, has too few examples for episodes with train pairs, or it's ambiguous and the policy says to skip it.
+fn generate_and_save(config: &GenerateConfig, variant: &TaskVariant) -> Result<Outcome> {
+    let Some(sizes) = config.sizes_for(variant) else {
+        return Ok(Outcome::Skipped(format!("it needs size at least {}", variant.min_size)));
+    };
+    if variant.min_colors > config.colors {
+        return Ok(Outcome::Skipped(format!("it needs at least {} colors", variant.min_colors)));
+    }
+    if !config.held_out && config.holdouts.iter().any(|holdout| holdout.matches_params(&variant.params)) {
+        return Ok(Outcome::Skipped("its parameters are held out".to_string()));
+    }
+    let seed = task_seed(config.seed, variant);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (examples, stats) = if config.enumerate {
+        if !variant.is_enumerable() {
+            return Ok(Outcome::Skipped("it can't be enumerated".to_string()));
+        }
+        match enumerate_task(variant, sizes, config.examples, |example| config.keeps(variant, example)) {
+            Some(enumerated) => enumerated,
+            None => return Ok(Outcome::Skipped(format!("it has more than {} examples", config.examples))),
+        }
+    } else {
+        // Held out attributes reject most examples on one side, so `2 * count` attempts are not enough, sampling goes on while new examples appear
+        let policy = if config.holdouts.is_empty() { config.underfill } else { UnderfillPolicy::Adaptive };
+        generate_task(&mut rng, config.examples, sizes, policy, |size, rng| {
+            variant.sample(config.sampling, size, rng).filter(|example| config.keeps(variant, example))
+        })
+    };
+    if stats.count == 0 && !config.holdouts.is_empty() {
+        let side = if config.held_out { "with" } else { "without" };
+        return Ok(Outcome::Skipped(format!("it has no examples {side} held out attributes")));
+    }
+    if stats.count < config.examples && config.underfill == UnderfillPolicy::Error && !config.enumerate {
+        return Err(Error::Underfilled { task: variant.name.clone(), requested: config.examples, stats });
+    }
+    // Each episode takes its train pairs from other examples of the task
+    if config.train_pairs > 0 && examples.len() <= config.train_pairs {
+        return Ok(Outcome::Skipped(format!("it has {} examples, but episodes with {} train pairs need at least {}", examples.len(), config.train_pairs, config.train_pairs + 1)));
+    }
+    let mut warning = None;
+    if let Some(conflict) = find_conflict(&examples) {
+        // Inputs of an inverse are outputs of the original task, so it's the original task which is not a function of its output
+        let inverse = variant.augmentations.contains(&Augmentation::Inverse);
+        let (policy, problem) = if inverse { (config.non_injective, "not invertible") } else { (config.ambiguous, "ambiguous") };
+        match policy {
+            AmbiguityPolicy::Warn => warning = Some(format!("{problem}: {conflict}")),
+            AmbiguityPolicy::Skip => return Ok(Outcome::Skipped(format!("it's {problem}, {conflict}"))),
+            AmbiguityPolicy::Error if inverse => return Err(Error::NotInjective { task: variant.name.clone(), conflict }),
+            AmbiguityPolicy::Error => return Err(Error::Ambiguous { task: variant.name.clone(), conflict }),
+        }
+    }
+    save_task(&config.output_dir, &variant.name, &examples, config.train_pairs, Some(variant), &Holdout::colors(&config.holdouts), seed)
+        .map_err(|err| Error::Task { task: variant.name.clone(), source: Box::new(err) })?;
+    Ok(Outcome::Saved { stats, warning })
+}
+
+/// Generates all registered tasks and saves each of them to its own json file, returns statistics of each generated task.
+///
+/// Tasks are distributed between `config.jobs` threads, and because each task has its own seed, files don't depend on the number of threads.
+pub fn generate_all(config: &GenerateConfig) -> Result<Vec<(String, GenerationStats)>> {
+    check_colors(config.colors)?;
+    let registry = registry_with(&config.augmentations);
+    let variants = registry.variants().iter()
+        .filter(|variant| matches_filter(&variant.name, config.filter.as_deref()))
+        .collect::<Vec<_>>();
+
+    let jobs = if config.jobs == 0 {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    } else {
+        config.jobs
+    };
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..variants.len()).map(|_| None).collect::<Vec<_>>());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(variants.len()) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(variant) = variants.get(i) else {
+                    break;
+                };
+                let result = with_colors(config.colors, || generate_and_save(config, variant));
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    // Messages are printed in registry order, so they don't depend on the number of threads too
+    let mut all_stats = vec![];
+    for (variant, result) in variants.iter().zip(results.into_inner().unwrap()) {
+        match result.expect("every task is processed by some thread")? {
+            Outcome::Skipped(reason) => eprintln!("skipping task `{}`: {reason}", variant.name),
+            Outcome::Saved { stats, warning } => {
+                if let Some(warning) = warning {
+                    eprintln!("warning: task `{}` is {warning}", variant.name);
+                }
+                if stats.count < config.examples && !config.enumerate {
+                    eprintln!("task `{}` is under-filled: {stats}", variant.name);
+                }
+                all_stats.push((variant.name.clone(), stats));
+            }
+        }
+    }
+    Ok(all_stats)
+}
+
+pub fn matches_filter(name: &str, filter: Option<&str>) -> bool {
+    filter.is_none_or(|filter| name.contains(filter))
+}
@@ -0,0 +1,74 @@
+// Exhaustive enumeration of inputs of tasks for small sizes
+
+use std::ops::Range;
+
+use crate::field::*;
+use crate::grid::{Color, Grid};
+
+/// Every field of `size` with cells from `values`, in lexicographic order of their indices in `values`. Values which are not colors of the palette are skipped.
+pub fn fields(size: usize, mut values: Vec<u8>) -> impl Iterator<Item = Grid> {
+    values.retain(|&value| Color::new(value).is_some());
+    let mut digits = (!values.is_empty() || size == 0).then(|| vec![0; size]);
+    std::iter::from_fn(move || {
+        let current = digits.as_mut()?;
+        let field = Grid::from_cells(current.iter().map(|&digit| values[digit]).collect()).expect("values are in the palette");
+        // Increment like a number with base `values.len()`, the last field is followed by nothing
+        match current.iter().rposition(|&digit| digit + 1 < values.len()) {
+            Some(i) => {
+                current[i] += 1;
+                current[i + 1..].fill(0);
+            }
+            None => digits = None,
+        }
+        Some(field)
+    })
+}
+
+/// Every block with length from `lengths`: solid blocks of each color of the palette, or blocks with every combination of colors of their pixels.
+fn blocks(lengths: Range<usize>, solid: bool) -> impl Iterator<Item = Grid> {
+    let colors = colors();
+    lengths.flat_map(move |len| -> Box<dyn Iterator<Item = Grid>> {
+        if solid {
+            Box::new((1..=colors).filter_map(move |color| gen_field_color(len, color)))
+        } else {
+            Box::new(fields(len, (1..=colors).collect()))
+        }
+    })
+}
+
+/// Inputs of `task_move_n_pix`.
+pub fn enumerate_move_n_pix(size: usize, move_pix: usize, solid: bool) -> impl Iterator<Item = Grid> {
+    let lengths = if size <= move_pix + 1 { 0..0 } else { 1..size - move_pix };
+    blocks(lengths, solid).flat_map(move |block| {
+        (0..=size - block.len() - move_pix).map(move |pos| write_block(pos, &block, gen_field(size)).expect("block fits"))
+    })
+}
+
+/// Inputs of `task_move_n_pix_wrapped`.
+pub fn enumerate_move_n_pix_wrapped(size: usize, solid: bool) -> impl Iterator<Item = Grid> {
+    blocks(1..size.max(1), solid).flat_map(move |block| (0..size).filter_map(move |pos| write_block_wrapped(pos, &block, gen_field(size))))
+}
+
+/// Inputs of `task_gravity`, `task_gravity_counting` and `task_gravity_one_step`: any field.
+pub fn enumerate_sparse_field(size: usize) -> impl Iterator<Item = Grid> {
+    fields(size, (0..=colors()).collect())
+}
+
+/// Inputs of `task_gravity_antigravity` and `task_gravity_weighted_colors`: any field of colors 1 and 2.
+pub fn enumerate_sparse_field_two_colors(size: usize) -> impl Iterator<Item = Grid> {
+    fields(size, vec![0, 1, 2])
+}
+
+/// Inputs of `task_two_points_and_fill`.
+pub fn enumerate_two_points_and_fill(size: usize) -> impl Iterator<Item = Grid> {
+    (1..=colors()).flat_map(move |color| {
+        (0..size).flat_map(move |pos1| {
+            (pos1 + 1..size).filter_map(move |pos2| {
+                let mut question = gen_field(size);
+                question.set(pos1, color).ok()?;
+                question.set(pos2, color).ok()?;
+                Some(question)
+            })
+        })
+    })
+}
@@ -0,0 +1,153 @@
+// Choosing train pairs for episodes
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::dataset::stable_hash;
+use crate::registry::Branches;
+use crate::tasks::Example;
+use crate::{Error, Result};
+
+/// How many random train sets are tried before the best of them is taken.
+const ATTEMPTS: usize = 10;
+
+/// What the train pairs of an episode show about the rule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct EpisodeMetadata {
+    /// Branch of the test example, if the task declares branches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_branch: Option<String>,
+    /// Branches shown by train pairs, in the order they are declared by the task.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<String>,
+    /// Train pairs are also explained by identity or a constant shift which gives a wrong answer for the test.
+    pub confounded: bool,
+    /// Colors of the episode were changed, `permutation[color]` is the new color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permutation: Option<Vec<u8>>,
+}
+
+/// Seed of the episode with given index, so every episode can be reproduced on its own.
+pub fn episode_seed(task_seed: u64, index: usize) -> u64 {
+    stable_hash(&[&task_seed.to_le_bytes(), &(index as u64).to_le_bytes()])
+}
+
+/// Shifts `input` by `offset` pixels to the right, pixels are either wrapped around or replaced by zeros.
+fn shift(input: &[u8], offset: isize, wrapped: bool) -> Vec<u8> {
+    let size = input.len() as isize;
+    (0..size)
+        .map(|i| {
+            let from = i - offset;
+            if wrapped {
+                input[from.rem_euclid(size) as usize]
+            } else if (0..size).contains(&from) {
+                input[from as usize]
+            } else {
+                0
+            }
+        })
+        .collect()
+}
+
+/// Whether some shift (identity is a shift by 0) explains all `train` pairs but gives a wrong answer for `test`.
+fn is_confounded(train: &[&Example], test: &Example) -> bool {
+    if train.is_empty() || train.iter().any(|example| example.input.len() != example.output.len()) {
+        return false;
+    }
+    let max_offset = train.iter().map(|example| example.input.len()).max().unwrap_or(0) as isize;
+    (-max_offset..=max_offset).any(|offset| {
+        [false, true].into_iter().any(|wrapped| {
+            train.iter().all(|example| shift(&example.input, offset, wrapped) == example.output[..])
+                && shift(&test.input, offset, wrapped) != test.output[..]
+        })
+    })
+}
+
+/// Chooses exactly `count` train examples for the episode with test `examples[test]`, returns their indices.
+///
+/// Train examples never have the same input as the test, so the answer can't be just looked up. Examples have random sizes, so train pairs usually have different sizes too.
+///
+/// If the task has `branches`, train pairs show the branch of the test first and then as many other branches as fit. Train sets which are also explained by a simpler rule (see [`EpisodeMetadata::confounded`]) are avoided when possible.
+pub fn choose_train(
+    examples: &[Example],
+    test: usize,
+    count: usize,
+    branches: Option<&Branches>,
+    seed: u64,
+) -> Result<(Vec<usize>, EpisodeMetadata)> {
+    let test_branch = branches.map(|branches| (branches.classify)(&examples[test]));
+    if count == 0 {
+        return Ok((vec![], EpisodeMetadata { test_branch: test_branch.map(String::from), ..Default::default() }));
+    }
+    let candidates = (0..examples.len())
+        .filter(|&i| i != test && examples[i].input != examples[test].input)
+        .collect::<Vec<_>>();
+    if candidates.len() < count {
+        return Err(Error::NotEnoughTrainPairs { needed: count, available: candidates.len() });
+    }
+    let classes = candidates.iter()
+        .map(|&i| branches.map(|branches| (branches.classify)(&examples[i])))
+        .collect::<Vec<_>>();
+    let wanted = match (branches, test_branch) {
+        (Some(branches), Some(test_branch)) => {
+            let mut wanted = vec![test_branch];
+            wanted.extend(branches.names.iter().copied().filter(|&name| name != test_branch));
+            wanted
+        }
+        _ => vec![],
+    };
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut order = (0..candidates.len()).collect::<Vec<_>>();
+    let mut best: Option<((bool, usize, bool), Vec<usize>)> = None;
+    for _ in 0..ATTEMPTS {
+        order.shuffle(&mut rng);
+        // One example of each wanted branch, then anything else
+        let mut chosen = vec![];
+        for &branch in &wanted {
+            if chosen.len() == count {
+                break;
+            }
+            if let Some(&i) = order.iter().find(|&&i| classes[i] == Some(branch)) {
+                chosen.push(i);
+            }
+        }
+        for &i in &order {
+            if chosen.len() == count {
+                break;
+            }
+            if !chosen.contains(&i) {
+                chosen.push(i);
+            }
+        }
+
+        chosen.shuffle(&mut rng);
+
+        let shows = |branch: &str| chosen.iter().any(|&i| classes[i] == Some(branch));
+        let covered = wanted.iter().filter(|&&branch| shows(branch)).count();
+        let train = chosen.iter().map(|&i| &examples[candidates[i]]).collect::<Vec<_>>();
+        let confounded = is_confounded(&train, &examples[test]);
+        let score = (test_branch.is_none_or(shows), covered, !confounded);
+        let done = score == (true, wanted.len().min(count), true);
+        if best.as_ref().is_none_or(|(best_score, _)| score > *best_score) {
+            best = Some((score, chosen));
+        }
+        if done {
+            break;
+        }
+    }
+
+    let (score, chosen) = best.expect("ATTEMPTS is not zero");
+    let shown = chosen.iter().filter_map(|&i| classes[i]).collect::<Vec<_>>();
+    let metadata = EpisodeMetadata {
+        test_branch: test_branch.map(String::from),
+        branches: branches
+            .map(|branches| branches.names.iter().filter(|name| shown.contains(name)).map(|name| name.to_string()).collect())
+            .unwrap_or_default(),
+        confounded: !score.2,
+        permutation: None,
+    };
+    Ok((chosen.into_iter().map(|i| candidates[i]).collect(), metadata))
+}
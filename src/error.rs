@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::checks::Conflict;
+use crate::dataset::GenerationStats;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// Task can't be added to the registry, for example because of duplicate name.
+    Registry(String),
+    UnknownTask(String),
+    /// All requested sizes are smaller than the task needs.
+    SizeTooSmall { task: String, min_size: usize },
+    /// Stream of examples can't be created, for example because all tasks have zero weight.
+    Stream(String),
+    /// There are not enough examples to choose train pairs of an episode.
+    NotEnoughTrainPairs { needed: usize, available: usize },
+    /// Error while processing the task.
+    Task { task: String, source: Box<Error> },
+    /// Task generated fewer unique examples than requested.
+    Underfilled { task: String, requested: usize, stats: GenerationStats },
+    /// Cell value is not a color of the palette with `colors` colors.
+    InvalidColor { color: u8, colors: u8 },
+    /// Palette of this number of colors is not supported.
+    InvalidPalette(u8),
+    /// Task uses fixed colors that are not in the palette.
+    TooFewColors { task: String, min_colors: u8 },
+    /// Block doesn't fit into the grid.
+    OutOfBounds { pos: usize, len: usize, size: usize },
+    /// File is valid json, but doesn't have the expected structure.
+    Format(String),
+    /// Task is an inverse of a task which maps different inputs to the same output.
+    NotInjective { task: String, conflict: Conflict },
+    /// Task has an input with two different outputs.
+    Ambiguous { task: String, conflict: Conflict },
+    /// Generated files didn't pass the check, details are printed before.
+    Check(String),
+    /// Splits of the dataset can't be generated together, for example because their sizes overlap.
+    Split(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "io error: {err}"),
+            Error::Json(err) => write!(f, "json error: {err}"),
+            Error::Registry(msg) => write!(f, "registry error: {msg}"),
+            Error::UnknownTask(name) => write!(f, "unknown task `{name}`"),
+            Error::Stream(msg) => write!(f, "stream error: {msg}"),
+            Error::NotEnoughTrainPairs { needed, available } => write!(f, "episode needs {needed} train pairs, but only {available} examples can be used"),
+            Error::Task { task, source } => write!(f, "task `{task}`: {source}"),
+            Error::SizeTooSmall { task, min_size } => write!(f, "task `{task}` needs size at least {min_size}"),
+            Error::Underfilled { task, requested, stats } => write!(f, "task `{task}` is under-filled, requested {requested}, got {stats}"),
+            Error::InvalidColor { color, colors } => write!(f, "color {color} is not in the palette 0..={colors}"),
+            Error::InvalidPalette(colors) => write!(f, "palette of {colors} colors is not supported, it should be 1..={}", crate::field::MAX_COLORS),
+            Error::TooFewColors { task, min_colors } => write!(f, "task `{task}` needs at least {min_colors} colors"),
+            Error::OutOfBounds { pos, len, size } => write!(f, "block of size {len} at {pos} doesn't fit into grid of size {size}"),
+            Error::Format(msg) => write!(f, "format error: {msg}"),
+            Error::NotInjective { task, conflict } => write!(f, "task `{task}` is not invertible: {conflict}"),
+            Error::Ambiguous { task, conflict } => write!(f, "task `{task}` is ambiguous: {conflict}"),
+            Error::Check(msg) => write!(f, "check failed: {msg}"),
+            Error::Split(msg) => write!(f, "split error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Json(err) => Some(err),
+            Error::Task { source, .. } => Some(source.as_ref()),
+            Error::Registry(_)
+            | Error::UnknownTask(_)
+            | Error::Stream(_)
+            | Error::SizeTooSmall { .. }
+            | Error::NotEnoughTrainPairs { .. }
+            | Error::Underfilled { .. }
+            | Error::InvalidColor { .. }
+            | Error::InvalidPalette(_)
+            | Error::TooFewColors { .. }
+            | Error::OutOfBounds { .. }
+            | Error::Format(_)
+            | Error::NotInjective { .. }
+            | Error::Ambiguous { .. }
+            | Error::Check(_)
+            | Error::Split(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
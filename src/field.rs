@@ -0,0 +1,137 @@
+// Helper functions for generating and editing 1D fields
+
+use rand::rngs::StdRng;
+use std::cell::Cell;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::grid::Grid;
+use crate::{Error, Result};
+
+/// Default number of colors, not counting the background.
+pub const COLORS: u8 = 9;
+/// Biggest palette that can be used, colors bigger than that don't have much sense for 1D tasks.
+pub const MAX_COLORS: u8 = 64;
+
+thread_local! {
+    static PALETTE: Cell<u8> = const { Cell::new(COLORS) };
+}
+
+/// Number of colors of the current thread, not counting the background. It's [`COLORS`] unless changed by [`with_colors`].
+pub fn colors() -> u8 {
+    PALETTE.with(Cell::get)
+}
+
+pub fn check_colors(colors: u8) -> Result<()> {
+    if (1..=MAX_COLORS).contains(&colors) {
+        Ok(())
+    } else {
+        Err(Error::InvalidPalette(colors))
+    }
+}
+
+/// Runs `f` with palette of `colors` colors on the current thread, generators and readers of grids use this palette.
+pub fn with_colors<T>(colors: u8, f: impl FnOnce() -> T) -> T {
+    struct Restore(u8);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            PALETTE.with(|palette| palette.set(self.0));
+        }
+    }
+
+    assert!(check_colors(colors).is_ok(), "palette of {colors} colors is not supported");
+    let _restore = Restore(PALETTE.with(|palette| palette.replace(colors)));
+    f()
+}
+
+pub fn random_color(rng: &mut StdRng) -> u8 {
+    rng.gen_range(1..=colors())
+}
+
+pub fn random_color_two(rng: &mut StdRng) -> u8 {
+    rng.gen_range(1..=2)
+}
+
+pub fn permute_color(color: u8, rng: &mut StdRng) -> u8 {
+    let colors = colors();
+    (color + rng.gen_range(1..=colors)) % (colors + 1)
+}
+
+pub fn permute_color_not_black(color: u8, rng: &mut StdRng) -> u8 {
+    let mut answer = permute_color(color, rng);
+    while answer == 0 {
+        answer = permute_color(color, rng);
+    }
+    answer
+}
+
+/// Random bijection of non-background colors which keeps `fixed` colors in place, `permutation[color]` is the new color.
+pub fn random_permutation(fixed: &[u8], rng: &mut StdRng) -> Vec<u8> {
+    let mut permutation = (0..=colors()).collect::<Vec<_>>();
+    let free = (1..=colors()).filter(|color| !fixed.contains(color)).collect::<Vec<_>>();
+    let mut shuffled = free.clone();
+    shuffled.shuffle(rng);
+    for (from, to) in free.into_iter().zip(shuffled) {
+        permutation[from as usize] = to;
+    }
+    permutation
+}
+
+/// Permutation which undoes `permutation`, `None` if it's not a bijection of the palette of the current thread.
+pub fn invert_permutation(permutation: &[u8]) -> Option<Vec<u8>> {
+    if permutation.len() != colors() as usize + 1 {
+        return None;
+    }
+    let mut inverse = vec![None; permutation.len()];
+    for (from, &to) in permutation.iter().enumerate() {
+        let slot = inverse.get_mut(to as usize)?;
+        if slot.is_some() {
+            return None;
+        }
+        *slot = Some(from as u8);
+    }
+    inverse.into_iter().collect()
+}
+
+pub fn gen_field(size: usize) -> Grid {
+    Grid::new(size)
+}
+
+pub fn gen_random_field(size: usize, rng: &mut StdRng) -> Grid {
+    Grid::from_cells((0..size).map(|_| random_color(rng)).collect()).expect("random colors are in the palette")
+}
+
+pub fn gen_random_sparse_field(size: usize, density: f64, rng: &mut StdRng) -> Grid {
+    Grid::from_cells((0..size).map(|_| if rng.gen_range(0.0..1.0) < density { random_color(rng) } else { 0 }).collect()).expect("random colors are in the palette")
+}
+
+/// Colors 1 and 2 must be in the palette.
+pub fn gen_random_sparse_field_two_colors(size: usize, density: f64, rng: &mut StdRng) -> Option<Grid> {
+    Grid::from_cells((0..size).map(|_| if rng.gen_range(0.0..1.0) < density { random_color_two(rng) } else { 0 }).collect()).ok()
+}
+
+/// `None` if the color is not in the palette.
+pub fn gen_field_color(size: usize, color: u8) -> Option<Grid> {
+    Grid::from_cells(vec![color; size]).ok()
+}
+
+/// `None` if the block doesn't fit into the field.
+pub fn write_block(pos: usize, block: &[u8], mut field: Grid) -> Option<Grid> {
+    field.write_block(pos, block).ok()?;
+    Some(field)
+}
+
+pub fn remove_color(color: u8, mut field: Grid) -> Grid {
+    field.retain(|&cell| cell != color);
+    field
+}
+
+/// `None` if the field is empty or the block has colors out of the palette.
+pub fn write_block_wrapped(pos: usize, block: &[u8], mut field: Grid) -> Option<Grid> {
+    let len = field.len();
+    for (i, &color) in block.iter().enumerate() {
+        field.set((pos + i).checked_rem(len)?, color).ok()?;
+    }
+    Some(field)
+}
@@ -0,0 +1,211 @@
+// Typed 1D grid and its colors
+
+use std::fmt;
+use std::ops::{Bound, Deref, Range, RangeBounds};
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::field::colors;
+use crate::{Error, Result};
+
+/// Color of one cell, `0` is the background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+pub struct Color(u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0);
+
+    /// Returns `None` if `value` is not in the palette of the current thread, see [`crate::field::with_colors`].
+    pub fn new(value: u8) -> Option<Color> {
+        (value <= colors()).then_some(Color(value))
+    }
+
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    pub fn is_background(self) -> bool {
+        self == Color::BLACK
+    }
+}
+
+impl TryFrom<u8> for Color {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Color> {
+        Color::new(value).ok_or(Error::InvalidColor { color: value, colors: colors() })
+    }
+}
+
+impl From<Color> for u8 {
+    fn from(color: Color) -> u8 {
+        color.0
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Solid run of one color, see [`Grid::runs`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub len: usize,
+    pub color: Color,
+}
+
+impl Run {
+    /// Position after the last cell of the run.
+    pub fn end(&self) -> usize {
+        self.start + self.len
+    }
+}
+
+/// Row of cells. It derefs to `[u8]`, so cells can be read in place, and they are changed only by methods which check bounds and the palette.
+///
+/// Construction and deserialization check that every cell is a [`Color`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default, Deserialize)]
+#[serde(from = "Vec<Color>")]
+pub struct Grid(Vec<u8>);
+
+impl Grid {
+    /// Grid of `size` background cells.
+    pub fn new(size: usize) -> Grid {
+        Grid(vec![0; size])
+    }
+
+    /// Checks that all cells are colors of the palette.
+    pub fn from_cells(cells: Vec<u8>) -> Result<Grid> {
+        check_cells(&cells)?;
+        Ok(Grid(cells))
+    }
+
+    pub fn get(&self, pos: usize) -> Option<Color> {
+        self.0.get(pos).map(|&cell| Color(cell))
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = Color> + '_ {
+        self.0.iter().map(|&cell| Color(cell))
+    }
+
+    /// Maximal runs of the same color, including background, from left to right.
+    pub fn runs(&self) -> impl Iterator<Item = Run> + '_ {
+        let mut start = 0;
+        std::iter::from_fn(move || {
+            let color = *self.0.get(start)?;
+            let len = self.0[start..].iter().take_while(|&&cell| cell == color).count();
+            let run = Run { start, len, color: Color(color) };
+            start += len;
+            Some(run)
+        })
+    }
+
+    /// Solid blocks, it's runs which are not background.
+    pub fn objects(&self) -> impl Iterator<Item = Run> + '_ {
+        self.runs().filter(|run| !run.color.is_background())
+    }
+
+    /// Cells of `range`, fails if it doesn't fit into the grid.
+    fn cells_mut(&mut self, range: impl RangeBounds<usize>) -> Result<&mut [u8]> {
+        let size = self.0.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => size,
+        };
+        let range: Range<usize> = start..end;
+        self.0.get_mut(range).ok_or(Error::OutOfBounds { pos: start, len: end.saturating_sub(start), size })
+    }
+
+    /// Writes `block` starting at `pos`, fails without changing anything if it doesn't fit or has colors out of the palette.
+    pub fn write_block(&mut self, pos: usize, block: &[u8]) -> Result<()> {
+        check_cells(block)?;
+        let end = pos.checked_add(block.len()).ok_or(Error::OutOfBounds { pos, len: block.len(), size: self.0.len() })?;
+        self.cells_mut(pos..end)?.copy_from_slice(block);
+        Ok(())
+    }
+
+    /// Sets the cell at `pos`, fails if it's out of the grid or `color` is out of the palette.
+    pub fn set(&mut self, pos: usize, color: u8) -> Result<()> {
+        let color = Color::try_from(color)?;
+        let size = self.0.len();
+        let cell = self.0.get_mut(pos).ok_or(Error::OutOfBounds { pos, len: 1, size })?;
+        *cell = color.value();
+        Ok(())
+    }
+
+    /// Paints cells of `range` with `color`, fails without changing anything if it doesn't fit or `color` is out of the palette.
+    pub fn fill(&mut self, range: impl RangeBounds<usize>, color: u8) -> Result<()> {
+        let color = Color::try_from(color)?;
+        self.cells_mut(range)?.fill(color.value());
+        Ok(())
+    }
+
+    /// Reverses cells of `range`, fails if it doesn't fit.
+    pub fn reverse(&mut self, range: impl RangeBounds<usize>) -> Result<()> {
+        self.cells_mut(range)?.reverse();
+        Ok(())
+    }
+
+    /// Keeps only cells for which `keep` is true, in the same order.
+    pub fn retain(&mut self, keep: impl FnMut(&u8) -> bool) {
+        self.0.retain(keep);
+    }
+
+    /// Rotates all cells to the right by `offset`, cells from the end come to the start.
+    pub fn rotate_right(&mut self, offset: usize) {
+        if !self.0.is_empty() {
+            let offset = offset % self.0.len();
+            self.0.rotate_right(offset);
+        }
+    }
+
+    pub fn into_cells(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+fn check_cells(cells: &[u8]) -> Result<()> {
+    match cells.iter().find(|&&cell| Color::new(cell).is_none()) {
+        Some(&cell) => Err(Error::InvalidColor { color: cell, colors: colors() }),
+        None => Ok(()),
+    }
+}
+
+/// Same as [`Grid::from_cells`].
+impl TryFrom<Vec<u8>> for Grid {
+    type Error = Error;
+
+    fn try_from(cells: Vec<u8>) -> Result<Grid> {
+        Grid::from_cells(cells)
+    }
+}
+
+impl From<Vec<Color>> for Grid {
+    fn from(colors: Vec<Color>) -> Grid {
+        Grid(colors.into_iter().map(u8::from).collect())
+    }
+}
+
+impl Deref for Grid {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Serialize for Grid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
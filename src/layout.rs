@@ -0,0 +1,116 @@
+// Sampling of object layouts without rejection, and the distribution of layouts of generated examples
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Serialize;
+
+use crate::grid::Grid;
+use crate::tasks::Example;
+
+/// Splits `total` into `parts` non-negative numbers, every split has the same probability.
+pub fn split_evenly(total: usize, parts: usize, rng: &mut StdRng) -> Vec<usize> {
+    if parts == 0 {
+        return vec![];
+    }
+    // Stars and bars: positions of `parts - 1` bars among `total + parts - 1` places
+    let mut bars = index::sample(rng, total + parts - 1, parts - 1).into_vec();
+    bars.sort_unstable();
+    let mut split = Vec::with_capacity(parts);
+    let mut prev = 0;
+    for (i, &bar) in bars.iter().enumerate() {
+        split.push(bar - i - prev);
+        prev = bar - i;
+    }
+    split.push(total - prev);
+    split
+}
+
+/// Lengths of `count` blocks from `lengths` whose sum is at most `budget`, in random order.
+///
+/// Each length is sampled from what's left after the smallest possible rest, so nothing is rejected. `None` if even the smallest blocks don't fit.
+pub fn sample_lengths(count: usize, lengths: RangeInclusive<usize>, budget: usize, rng: &mut StdRng) -> Option<Vec<usize>> {
+    let (min, max) = (*lengths.start(), *lengths.end());
+    let mut left = budget.checked_sub(count * min)?;
+    let mut sampled = Vec::with_capacity(count);
+    for _ in 0..count {
+        let extra = rng.gen_range(0..=left.min(max - min));
+        sampled.push(min + extra);
+        left -= extra;
+    }
+    sampled.shuffle(rng);
+    Some(sampled)
+}
+
+/// Places blocks of `lengths` in this order, with at least `min_gaps[i]` empty cells between block `i` and the next one.
+///
+/// Free space is split evenly between all gaps and both borders. Returns `(start, length)` of each block, `None` if they don't fit into `size`.
+pub fn place_blocks(size: usize, lengths: &[usize], min_gaps: &[usize], rng: &mut StdRng) -> Option<Vec<(usize, usize)>> {
+    debug_assert_eq!(min_gaps.len() + 1, lengths.len().max(1), "every pair of neighbour blocks has its gap");
+    let used = lengths.iter().sum::<usize>() + min_gaps.iter().sum::<usize>();
+    let free = split_evenly(size.checked_sub(used)?, lengths.len() + 1, rng);
+    let mut blocks = Vec::with_capacity(lengths.len());
+    let mut pos = 0;
+    for (i, &len) in lengths.iter().enumerate() {
+        pos += free[i];
+        blocks.push((pos, len));
+        pos += len + min_gaps.get(i).copied().unwrap_or(0);
+    }
+    Some(blocks)
+}
+
+/// Field of `size` with blocks `(start, length)` of `color`, `None` if a block doesn't fit or the color is out of the palette.
+pub fn render_blocks(size: usize, blocks: &[(usize, usize)], color: u8) -> Option<Grid> {
+    let mut field = Grid::new(size);
+    for &(start, len) in blocks {
+        field.fill(start..start + len, color).ok()?;
+    }
+    Some(field)
+}
+
+/// How many blocks of `min_len` with gaps of `min_gap` fit into `size`.
+pub fn max_blocks(size: usize, min_len: usize, min_gap: usize) -> usize {
+    (size + min_gap) / (min_len + min_gap)
+}
+
+/// Distribution of layouts of inputs: how many objects they have and how long the objects are.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct LayoutStats {
+    /// Number of examples by the number of objects in the input.
+    pub objects: BTreeMap<usize, usize>,
+    /// Number of objects by their length.
+    pub lengths: BTreeMap<usize, usize>,
+}
+
+impl LayoutStats {
+    pub fn of(examples: &[Example]) -> LayoutStats {
+        let mut stats = LayoutStats::default();
+        for example in examples {
+            let mut count = 0;
+            for object in example.input.objects() {
+                *stats.lengths.entry(object.len).or_default() += 1;
+                count += 1;
+            }
+            *stats.objects.entry(count).or_default() += 1;
+        }
+        stats
+    }
+}
+
+impl fmt::Display for LayoutStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percents = |counts: &BTreeMap<usize, usize>| {
+            let total = counts.values().sum::<usize>().max(1);
+            counts.iter()
+                .map(|(key, count)| format!("{key}: {:.0}%", 100.0 * *count as f64 / total as f64))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        write!(f, "objects {{{}}}, lengths {{{}}}", percents(&self.objects), percents(&self.lengths))
+    }
+}
@@ -0,0 +1,61 @@
+//! Generator of 1D ARC-AGI like tasks.
+//!
+//! Every task is a `task_*` function from [`tasks`], and [`registry()`] lists all of them with their parameters and augmentations.
+//!
+//! ```
+//! let examples = arc_1d::generate("gravity_left", 10, 42).unwrap();
+//! let json = arc_1d::to_json(&examples, 3, 42).unwrap();
+//! ```
+
+pub mod checks;
+pub mod dataset;
+pub mod enumerate;
+pub mod episode;
+mod error;
+pub mod field;
+pub mod grid;
+pub mod layout;
+pub mod original_1d_arc;
+pub mod registry;
+pub mod solvers;
+pub mod splits;
+pub mod stream;
+pub mod tasks;
+pub mod visualization;
+pub mod wrong_pairs;
+
+pub use dataset::{generate_task, ArcTask2D, Example2D, SizeRange};
+pub use error::{Error, Result};
+pub use grid::{Color, Grid};
+pub use registry::{registry, registry_with, Registry, TaskVariant};
+pub use tasks::Example;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Generates up to `count` unique examples of the task with given name, they are the same as in the file written by `generate` command with this seed.
+pub fn generate(task: &str, count: usize, seed: u64) -> Result<Vec<Example>> {
+    generate_with_sizes(task, count, seed, SizeRange::default())
+}
+
+/// Same as [`generate`], but with field sizes from `sizes`, sizes that are too small for the task are not used.
+///
+/// Colors are from the palette of the current thread, see [`field::with_colors`].
+pub fn generate_with_sizes(task: &str, count: usize, seed: u64, sizes: SizeRange) -> Result<Vec<Example>> {
+    let registry = registry_with(&registry::Augmentation::ALL);
+    let variant = registry.get(task).ok_or_else(|| Error::UnknownTask(task.to_string()))?;
+    let sizes = sizes.at_least(variant.min_size).ok_or_else(|| Error::SizeTooSmall { task: task.to_string(), min_size: variant.min_size })?;
+    if variant.min_colors > field::colors() {
+        return Err(Error::TooFewColors { task: task.to_string(), min_colors: variant.min_colors });
+    }
+    let mut rng = StdRng::seed_from_u64(dataset::task_seed(seed, variant));
+    let (examples, _stats) = generate_task(&mut rng, count, sizes, dataset::UnderfillPolicy::Warn, |size, rng| variant.generate(size, rng));
+    Ok(examples)
+}
+
+/// Serializes examples the same way as they are saved in `tasks/*.json`, each episode has `train_pairs` train examples chosen using `seed`.
+///
+/// The task is unknown here, so its branches are not covered and colors are not permuted, use [`dataset::to_arc_tasks`] with the [`TaskVariant`] for that.
+pub fn to_json(examples: &[Example], train_pairs: usize, seed: u64) -> Result<String> {
+    Ok(serde_json::to_string(&dataset::to_arc_tasks(examples, train_pairs, None, &[], seed)?)?)
+}
@@ -0,0 +1,294 @@
+// Layout of the original 1D-ARC dataset: a folder for each category and a json file for each problem
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::dataset::{matches_filter, save_json_to_file, stable_hash, ArcTask2D};
+use crate::field::{check_colors, with_colors};
+use crate::layout::LayoutStats;
+use crate::registry::{registry_with, Augmentation, TaskVariant};
+use crate::tasks::Example;
+use crate::visualization::read_tasks;
+use crate::{Error, Result};
+
+/// Categories of 1D-ARC with tasks of this crate which reproduce them, categories without tasks are not implemented here.
+pub const CATEGORIES: &[(&str, &[&str])] = &[
+    ("1d_denoising_1c", &["block_and_noise_remove"]),
+    ("1d_denoising_mc", &["block_and_noise_remove_inside"]),
+    ("1d_fill", &["two_points_and_fill"]),
+    ("1d_flip", &["reflect_block_with_border_pixel"]),
+    ("1d_hollow", &[]),
+    ("1d_mirror", &["reflect_block_around_dot"]),
+    ("1d_move_1p", &["move_1pix_solid_right"]),
+    ("1d_move_2p", &["move_2pix_solid_right"]),
+    ("1d_move_2p_dp", &["block_touch_dot_2_pix"]),
+    ("1d_move_3p", &["move_3pix_solid_right"]),
+    ("1d_move_dp", &["block_touch_dot"]),
+    ("1d_padded_fill", &[]),
+    ("1d_pcopy_1c", &["copy_block_to_dots"]),
+    ("1d_pcopy_mc", &["copy_block_to_dots_colors"]),
+    ("1d_recolor_cmp", &["paint_biggest_block"]),
+    ("1d_recolor_cnt", &[]),
+    ("1d_recolor_oe", &[]),
+    ("1d_scale_dp", &["block_scale_to_dot"]),
+];
+
+/// Category of 1D-ARC which the task reproduces, see [`CATEGORIES`].
+pub fn category(task: &str) -> Option<&'static str> {
+    CATEGORIES.iter()
+        .find(|(_, tasks)| tasks.contains(&task))
+        .map(|(category, _)| *category)
+}
+
+/// Exports task files with `colors` colors from `tasks_dir` whose names contain `filter` to `output_dir` in the layout of 1D-ARC, returns folder of each exported task.
+///
+/// Every episode becomes `<folder>/<folder>_<index>.json` with only `train` and `test`, at most `limit` of them for each task. Folder is the 1D-ARC category of the task, or its own name if it has none. Folder of each task is also saved to `names.json`.
+///
+/// Problems of 1D-ARC have train pairs, so tasks whose episodes have none are skipped with a message.
+pub fn export(tasks_dir: &Path, output_dir: &Path, filter: Option<&str>, limit: Option<usize>, colors: u8) -> Result<BTreeMap<String, String>> {
+    check_colors(colors)?;
+    with_colors(colors, || {
+        let registry = registry_with(&Augmentation::ALL);
+        let mut names = BTreeMap::new();
+        for variant in registry.variants() {
+            let path = tasks_dir.join(format!("{}.json", variant.name));
+            if !matches_filter(&variant.name, filter) || !path.exists() {
+                continue;
+            }
+            let tasks = read_tasks(&path)?;
+            if tasks.iter().any(|task| task.train.is_empty()) {
+                eprintln!("skipping task `{}`: its episodes have no train pairs, generate it with `--train-pairs`", variant.name);
+                continue;
+            }
+            let folder = category(&variant.name).unwrap_or(&variant.name);
+            let dir = output_dir.join(folder);
+            fs::create_dir_all(&dir)?;
+            for (i, task) in tasks.into_iter().take(limit.unwrap_or(usize::MAX)).enumerate() {
+                let problem = ArcTask2D { metadata: None, ..task };
+                save_json_to_file(&problem, &dir.join(format!("{folder}_{i}.json")))?;
+            }
+            names.insert(variant.name.clone(), folder.to_string());
+        }
+        fs::create_dir_all(output_dir)?;
+        save_json_to_file(&names, &output_dir.join("names.json"))?;
+        Ok(names)
+    })
+}
+
+/// Problem of 1D-ARC, its train and test pairs are all examples of the category.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Problem {
+    /// Name of the json file.
+    pub file: String,
+    pub examples: Vec<Example>,
+}
+
+fn read_problem(path: &Path) -> Result<Problem> {
+    let read = || -> Result<Vec<Example>> {
+        let task: ArcTask2D = serde_json::from_str(&fs::read_to_string(path)?)?;
+        task.train.into_iter().chain(task.test)
+            .map(|example| match (&example.input[..], &example.output[..]) {
+                ([input], [output]) => Ok(Example { input: input.clone(), output: output.clone() }),
+                _ => Err(Error::Format(format!("example has {} input rows and {} output rows, expected one of each", example.input.len(), example.output.len()))),
+            })
+            .collect()
+    };
+    let examples = read().map_err(|err| Error::Task { task: path.display().to_string(), source: Box::new(err) })?;
+    Ok(Problem { file: path.file_name().unwrap().to_string_lossy().into_owned(), examples })
+}
+
+/// Reads a local copy of 1D-ARC, like its `dataset` folder or the output of [`export`], problems of each category are sorted by file name.
+///
+/// Grids may have `colors` colors besides the background.
+pub fn read_dataset(dataset_dir: &Path, colors: u8) -> Result<BTreeMap<String, Vec<Problem>>> {
+    check_colors(colors)?;
+    with_colors(colors, || {
+        let mut categories = BTreeMap::new();
+        for entry in fs::read_dir(dataset_dir)? {
+            let dir = entry?.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            let mut paths = fs::read_dir(&dir)?
+                .map(|entry| Ok(entry?.path()))
+                .collect::<Result<Vec<_>>>()?;
+            paths.retain(|path| path.extension().is_some_and(|extension| extension == "json"));
+            paths.sort();
+            let problems = paths.iter().map(|path| read_problem(path)).collect::<Result<Vec<_>>>()?;
+            if !problems.is_empty() {
+                categories.insert(dir.file_name().unwrap().to_string_lossy().into_owned(), problems);
+            }
+        }
+        if categories.is_empty() {
+            return Err(Error::Format(format!("no category folders with json files in {}", dataset_dir.display())));
+        }
+        Ok(categories)
+    })
+}
+
+/// Example of 1D-ARC which is explained by a task, but which its generator doesn't produce.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gap {
+    pub file: String,
+    pub task: String,
+    pub example: Example,
+    /// What samples of the task never have, like `object of length 12`.
+    pub reason: String,
+}
+
+/// How tasks of this crate cover one category of 1D-ARC.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategoryParity {
+    pub category: String,
+    pub problems: usize,
+    pub examples: usize,
+    /// Tasks from [`CATEGORIES`] with the number of examples they explain.
+    pub mapped: Vec<(String, usize)>,
+    /// Tasks which explain every example of the category, without augmented variants of tasks which are already listed.
+    pub reproduced_by: Vec<String>,
+    /// Task which explains the most examples, and their number, if it explains any.
+    pub closest: Option<(String, usize)>,
+    /// Examples explained by the reproducing tasks, or by the closest one, which none of them produces.
+    pub gaps: Vec<Gap>,
+}
+
+/// Parity of this crate with 1D-ARC, see [`parity`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Parity {
+    pub categories: Vec<CategoryParity>,
+    /// Categories of the dataset which no task reproduces.
+    pub unimplemented: Vec<String>,
+    /// Categories from [`CATEGORIES`] which are not in the dataset.
+    pub missing: Vec<String>,
+}
+
+/// What `samples` examples of the task have at one size, used to find gaps.
+struct Support {
+    layouts: LayoutStats,
+    colors: BTreeSet<u8>,
+}
+
+impl Support {
+    fn sample(variant: &TaskVariant, size: usize, samples: usize, seed: u64) -> Support {
+        let mut rng = StdRng::seed_from_u64(stable_hash(&[&seed.to_le_bytes(), variant.name.as_bytes(), &size.to_le_bytes()]));
+        let examples: Vec<Example> = (0..samples).filter_map(|_| variant.generate(size, &mut rng)).collect();
+        let colors = examples.iter().flat_map(|example| variant.attributes(example).colors).collect();
+        Support { layouts: LayoutStats::of(&examples), colors }
+    }
+
+    /// Why the example is never sampled, `None` if it could be.
+    fn gap(&self, variant: &TaskVariant, example: &Example) -> Option<String> {
+        let size = example.input.len();
+        if size < variant.min_size {
+            return Some(format!("size {size} is below the minimum {}", variant.min_size));
+        }
+        if self.layouts.objects.is_empty() {
+            return Some(format!("no examples of size {size} are sampled"));
+        }
+        let objects = example.input.objects().count();
+        if !self.layouts.objects.contains_key(&objects) {
+            return Some(format!("input with {objects} objects"));
+        }
+        if let Some(object) = example.input.objects().find(|object| !self.layouts.lengths.contains_key(&object.len)) {
+            return Some(format!("object of length {}", object.len));
+        }
+        if let Some(color) = variant.attributes(example).colors.into_iter().find(|color| !self.colors.contains(color)) {
+            return Some(format!("color {color}"));
+        }
+        None
+    }
+}
+
+/// Whether `variant` is `other` with more augmentations, the description and parameters tell the original task.
+fn is_augmented_from(variant: &TaskVariant, other: &TaskVariant) -> bool {
+    variant.description == other.description
+        && variant.params == other.params
+        && variant.augmentations.len() > other.augmentations.len()
+        && other.augmentations.iter().all(|augmentation| variant.augmentations.contains(augmentation))
+}
+
+/// Checks every example of the local copy of 1D-ARC in `dataset_dir`, which has `colors` colors, against the reference solvers of all tasks.
+///
+/// Gaps are found by sampling `samples` examples of the task at the size of the example with `seed`, so they are only what the samples never have: sizes, numbers of objects, lengths of objects and colors.
+pub fn parity(dataset_dir: &Path, samples: usize, seed: u64, colors: u8) -> Result<Parity> {
+    check_colors(colors)?;
+    with_colors(colors, || {
+        let dataset = read_dataset(dataset_dir, colors)?;
+        let registry = registry_with(&Augmentation::ALL);
+        let mut supports: HashMap<(&str, usize), Support> = HashMap::new();
+        let mut parity = Parity::default();
+        for (category, problems) in &dataset {
+            let examples: Vec<(&str, &Example)> = problems.iter()
+                .flat_map(|problem| problem.examples.iter().map(|example| (problem.file.as_str(), example)))
+                .collect();
+            let explained: Vec<(&TaskVariant, Vec<bool>)> = registry.variants().iter()
+                .map(|variant| (variant, examples.iter().map(|(_, example)| variant.verify(example)).collect()))
+                .collect();
+            let count = |explains: &[bool]| explains.iter().filter(|&&explains| explains).count();
+
+            let mapped_tasks = CATEGORIES.iter().find(|(name, _)| name == category).map_or(&[][..], |(_, tasks)| tasks);
+            let mapped = explained.iter()
+                .filter(|(variant, _)| mapped_tasks.contains(&variant.name.as_str()))
+                .map(|(variant, explains)| (variant.name.clone(), count(explains)))
+                .collect();
+            let reproducing: Vec<_> = explained.iter().filter(|(_, explains)| count(explains) == examples.len()).collect();
+            // The first task with the most examples, `max_by_key` would take the last one
+            let closest = explained.iter()
+                .filter(|(_, explains)| count(explains) > 0)
+                .fold(None, |best: Option<&(&TaskVariant, Vec<bool>)>, candidate| match best {
+                    Some(best) if count(&best.1) >= count(&candidate.1) => Some(best),
+                    _ => Some(candidate),
+                });
+
+            let candidates = if reproducing.is_empty() { closest.into_iter().collect() } else { reproducing.clone() };
+            let mut gaps = vec![];
+            for (i, &(file, example)) in examples.iter().enumerate() {
+                let mut reason = None;
+                for (variant, explains) in &candidates {
+                    if !explains[i] {
+                        continue;
+                    }
+                    let size = example.input.len();
+                    let support = supports.entry((&variant.name, size)).or_insert_with(|| Support::sample(variant, size, samples, seed));
+                    match support.gap(variant, example) {
+                        Some(gap) => {
+                            reason.get_or_insert((variant, gap));
+                        }
+                        None => {
+                            reason = None;
+                            break;
+                        }
+                    }
+                }
+                if let Some((variant, reason)) = reason {
+                    gaps.push(Gap { file: file.to_string(), task: variant.name.clone(), example: example.clone(), reason });
+                }
+            }
+
+            if reproducing.is_empty() {
+                parity.unimplemented.push(category.clone());
+            }
+            parity.categories.push(CategoryParity {
+                category: category.clone(),
+                problems: problems.len(),
+                examples: examples.len(),
+                mapped,
+                reproduced_by: reproducing.iter()
+                    .filter(|(variant, _)| !reproducing.iter().any(|(other, _)| is_augmented_from(variant, other)))
+                    .map(|(variant, _)| variant.name.clone())
+                    .collect(),
+                closest: closest.map(|(variant, explains)| (variant.name.clone(), count(explains))),
+                gaps,
+            });
+        }
+        parity.missing = CATEGORIES.iter()
+            .filter(|(category, _)| !dataset.contains_key(*category))
+            .map(|(category, _)| category.to_string())
+            .collect();
+        Ok(parity)
+    })
+}
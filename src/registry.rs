@@ -0,0 +1,1077 @@
+// Registry of all tasks
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::enumerate::*;
+use crate::field::colors;
+use crate::grid::Grid;
+use crate::solvers::*;
+use crate::tasks::*;
+use crate::{Error, Result};
+
+/// Change of examples that keeps the rule of a task, so it makes a new task from an old one.
+///
+/// Augmentations are applied one after another, in the order they are listed in the task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Augmentation {
+    /// Reverses input and output.
+    Mirror,
+    /// Swaps input and output.
+    Inverse,
+    /// Permutes colors which are not fixed by the task.
+    ///
+    /// One permutation is chosen for each episode, see [`crate::dataset::to_arc_tasks`], so it doesn't change the example itself.
+    Colors,
+    /// Rotates input and output by the same random offset, the task should work on a cyclic field.
+    Shift,
+    /// Swaps background with a random color which is not fixed by the task.
+    Background,
+    /// Repeats every pixel two times, so input and output become two times longer.
+    Upscale,
+}
+
+impl Augmentation {
+    /// All augmentations in the order of their suffixes in task names.
+    pub const ALL: [Augmentation; 6] = [
+        Augmentation::Mirror,
+        Augmentation::Inverse,
+        Augmentation::Colors,
+        Augmentation::Shift,
+        Augmentation::Background,
+        Augmentation::Upscale,
+    ];
+
+    /// Placeholder in the name of a task which always has both original and augmented variants.
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            Augmentation::Mirror => "{dir}",
+            Augmentation::Inverse => "{inv}",
+            Augmentation::Colors => "{perm}",
+            Augmentation::Shift => "{shift}",
+            Augmentation::Background => "{bg}",
+            Augmentation::Upscale => "{x2}",
+        }
+    }
+
+    /// What placeholder becomes in the original and augmented variants, `{dir}` becomes `right` or `left`, others are removed and the augmented variant gets the suffix instead.
+    pub fn parts(self) -> [String; 2] {
+        match self {
+            Augmentation::Mirror => ["right".to_string(), "left".to_string()],
+            _ => [String::new(), String::new()],
+        }
+    }
+
+    /// Suffix of the name of an augmented task, suffixes are in the order of [`Augmentation::ALL`].
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Augmentation::Mirror => "mirror",
+            Augmentation::Inverse => "inv",
+            Augmentation::Colors => "perm",
+            Augmentation::Shift => "shift",
+            Augmentation::Background => "bg",
+            Augmentation::Upscale => "x2",
+        }
+    }
+
+    /// Branches of the original task are still found correctly after this augmentation.
+    fn keeps_branches(self) -> bool {
+        matches!(self, Augmentation::Mirror | Augmentation::Colors)
+    }
+
+    /// Applies augmentation to the example, `fixed_colors` of the task are never changed.
+    pub fn apply(self, example: Example, fixed_colors: &[u8], rng: &mut StdRng) -> Option<Example> {
+        match self {
+            Augmentation::Mirror => task_mirror(Some(example)),
+            Augmentation::Inverse => task_inverse(Some(example)),
+            Augmentation::Colors => Some(example),
+            Augmentation::Shift => {
+                if example.input.len() != example.output.len() || example.input.is_empty() {
+                    return None;
+                }
+                let offset = rng.gen_range(0..example.input.len());
+                task_cyclic_shift(Some(example), offset)
+            }
+            Augmentation::Background => {
+                let candidates = (1..=colors()).filter(|color| !fixed_colors.contains(color)).collect::<Vec<_>>();
+                task_swap_background(Some(example), *candidates.choose(rng)?)
+            }
+            Augmentation::Upscale => task_upscale(Some(example), 2),
+        }
+    }
+
+    /// Every example that [`Augmentation::apply`] can give for the example.
+    pub fn outcomes(self, example: Example, fixed_colors: &[u8]) -> Vec<Example> {
+        match self {
+            Augmentation::Shift => {
+                if example.input.len() != example.output.len() {
+                    return vec![];
+                }
+                (0..example.input.len()).filter_map(|offset| task_cyclic_shift(Some(example.clone()), offset)).collect()
+            }
+            Augmentation::Background => (1..=colors())
+                .filter(|color| !fixed_colors.contains(color))
+                .filter_map(|color| task_swap_background(Some(example.clone()), color))
+                .collect(),
+            Augmentation::Mirror => task_mirror(Some(example)).into_iter().collect(),
+            Augmentation::Inverse => task_inverse(Some(example)).into_iter().collect(),
+            Augmentation::Colors => vec![example],
+            Augmentation::Upscale => task_upscale(Some(example), 2).into_iter().collect(),
+        }
+    }
+
+    /// Changes the input of the augmented task back to the input of the original task, `None` if it can't be done from the input alone.
+    fn undo(self, input: &Grid) -> Option<Grid> {
+        match self {
+            Augmentation::Mirror => Grid::from_cells(input.iter().rev().copied().collect()).ok(),
+            Augmentation::Colors | Augmentation::Shift => Some(input.clone()),
+            Augmentation::Upscale => downscale(input, 2),
+            Augmentation::Inverse | Augmentation::Background => None,
+        }
+    }
+
+    /// Changes the output of the original task to the output of the augmented task, after [`Augmentation::undo`].
+    fn redo(self, output: &Grid) -> Option<Grid> {
+        match self {
+            Augmentation::Mirror => Grid::from_cells(output.iter().rev().copied().collect()).ok(),
+            Augmentation::Colors | Augmentation::Shift => Some(output.clone()),
+            Augmentation::Upscale => Grid::from_cells(output.iter().flat_map(|&cell| std::iter::repeat_n(cell, 2)).collect()).ok(),
+            Augmentation::Inverse | Augmentation::Background => None,
+        }
+    }
+}
+
+/// Inverse of [`task_upscale`], `None` if the grid is not upscaled by `factor`.
+fn downscale(grid: &Grid, factor: usize) -> Option<Grid> {
+    if !grid.len().is_multiple_of(factor) || grid.chunks(factor).any(|chunk| chunk.iter().any(|&cell| cell != chunk[0])) {
+        return None;
+    }
+    Grid::from_cells(grid.chunks(factor).map(|chunk| chunk[0]).collect()).ok()
+}
+
+impl FromStr for Augmentation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        Augmentation::ALL.into_iter()
+            .find(|augmentation| augmentation.suffix() == s)
+            .ok_or_else(|| format!("unknown augmentation `{s}`"))
+    }
+}
+
+/// How examples of a task are sampled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sampling {
+    /// Sample the example and reject it if it doesn't fit the size, so some layouts are more likely to be rejected than others.
+    #[default]
+    Rejection,
+    /// Sample the layout of objects so that it fits the size, then render it, tasks without such generator are sampled with rejection.
+    Constructive,
+}
+
+impl FromStr for Sampling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "rejection" => Ok(Sampling::Rejection),
+            "constructive" => Ok(Sampling::Constructive),
+            _ => Err(format!("unknown sampling `{s}`, expected `rejection` or `constructive`")),
+        }
+    }
+}
+
+/// Attributes of an example that can be held out of a dataset split, see [`TaskVariant::attributes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attributes {
+    /// Size of the input.
+    pub size: usize,
+    /// Colors of the input and output without background. Tasks which permute colors keep held out colors in place, so they are the same after the permutation.
+    pub colors: BTreeSet<u8>,
+    /// Positions where objects of the input start.
+    pub starts: Vec<usize>,
+    pub params: Params,
+}
+
+/// One point of the parameter grid of a task.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(pub Vec<(&'static str, usize)>);
+
+impl Params {
+    pub fn get(&self, key: &str) -> usize {
+        self.0.iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("unknown task parameter `{key}`"))
+    }
+}
+
+pub fn no_params() -> Vec<Params> {
+    vec![Params::default()]
+}
+
+pub fn param_grid(key: &'static str, values: impl IntoIterator<Item = usize>) -> Vec<Params> {
+    values.into_iter().map(|value| Params(vec![(key, value)])).collect()
+}
+
+/// Different cases of the task that should all be shown in train pairs, so the rule can be derived from them.
+#[derive(Clone, Copy, Debug)]
+pub struct Branches {
+    pub names: &'static [&'static str],
+    /// Finds the branch of an example, returns one of `names`.
+    pub classify: fn(&Example) -> &'static str,
+}
+
+/// Generates an example of given size with given parameters, `None` if the sampled example doesn't fit.
+pub type Generator = fn(usize, &Params, &mut StdRng) -> Option<Example>;
+
+/// Lists inputs of given size with given parameters, colors are from the palette of the current thread.
+pub type Enumerator = fn(usize, &Params) -> Box<dyn Iterator<Item = Grid>>;
+
+/// Description of a `task_*` function and all the tasks that are made from it.
+pub struct TaskDef {
+    /// Name template, `{key}` is replaced by the value of parameter `key`, and augmentations replace their own placeholders.
+    pub name: &'static str,
+    pub description: &'static str,
+    pub params: Vec<Params>,
+    /// Smallest field size for which the task can generate something.
+    pub min_size: fn(&Params) -> usize,
+    /// Output has the same length as input.
+    pub same_size: bool,
+    /// Smallest palette which has all fixed colors of the task and enough colors to be different where needed.
+    pub min_colors: u8,
+    /// Colors that have special meaning in the task, they are never permuted.
+    pub fixed_colors: &'static [u8],
+    /// Augmentations with placeholders in the name, every task has both variants of them.
+    pub augmentations: &'static [Augmentation],
+    /// Other augmentations that keep the rule of the task, they are added as name suffixes only when requested.
+    ///
+    /// Augmentations which the generator already covers, like mirroring a task which picks a random side, would only duplicate the task, so they are not declared.
+    pub symmetries: &'static [Augmentation],
+    pub branches: Option<Branches>,
+    pub generate: Generator,
+    /// Generator which samples the layout first and never rejects for sizes from `min_size`, see [`Sampling::Constructive`].
+    pub construct: Option<Generator>,
+    /// Every input that `generate` can produce for the size, see [`TaskVariant::enumerate`].
+    pub enumerate: Option<Enumerator>,
+    /// Reference solver of the original task, see [`crate::solvers`].
+    pub solve: fn(&Grid, &Params) -> Option<Grid>,
+}
+
+/// Single task with fixed parameters and augmentations, it's saved to its own json file.
+pub struct TaskVariant {
+    pub name: String,
+    pub description: &'static str,
+    pub params: Params,
+    pub min_size: usize,
+    pub same_size: bool,
+    pub min_colors: u8,
+    pub fixed_colors: &'static [u8],
+    /// Augmentations applied to examples of the original task, in order.
+    pub augmentations: Vec<Augmentation>,
+    pub branches: Option<Branches>,
+    generate: Generator,
+    construct: Option<Generator>,
+    enumerate: Option<Enumerator>,
+    solve: fn(&Grid, &Params) -> Option<Grid>,
+}
+
+impl TaskVariant {
+    pub fn generate(&self, size: usize, rng: &mut StdRng) -> Option<Example> {
+        self.sample(Sampling::Rejection, size, rng)
+    }
+
+    /// Generates an example with the constructive generator if it's requested and the task has one, otherwise the same as [`TaskVariant::generate`].
+    pub fn sample(&self, sampling: Sampling, size: usize, rng: &mut StdRng) -> Option<Example> {
+        let generate = match (sampling, self.construct) {
+            (Sampling::Constructive, Some(construct)) => construct,
+            _ => self.generate,
+        };
+        let mut example = generate(size, &self.params, rng)?;
+        for augmentation in &self.augmentations {
+            example = augmentation.apply(example, self.fixed_colors, rng)?;
+        }
+        Some(example)
+    }
+
+    /// Output of the reference solver, `None` if the input can't be an input of the task.
+    ///
+    /// Inverse and swapped background can't be undone from the input alone, so variants with them are only checked by [`TaskVariant::verify`].
+    pub fn solve(&self, input: &Grid) -> Option<Grid> {
+        let mut input = input.clone();
+        for augmentation in self.augmentations.iter().rev() {
+            input = augmentation.undo(&input)?;
+        }
+        let mut output = (self.solve)(&input, &self.params)?;
+        for augmentation in &self.augmentations {
+            output = augmentation.redo(&output)?;
+        }
+        Some(output)
+    }
+
+    /// Whether the reference solver gives `example.output` for `example.input`, augmentations are undone one by one.
+    pub fn verify(&self, example: &Example) -> bool {
+        self.verify_with(&self.augmentations, example.clone())
+    }
+
+    fn verify_with(&self, augmentations: &[Augmentation], example: Example) -> bool {
+        let Some((augmentation, rest)) = augmentations.split_last() else {
+            return (self.solve)(&example.input, &self.params).as_ref() == Some(&example.output);
+        };
+        match augmentation {
+            Augmentation::Mirror => task_mirror(Some(example)).is_some_and(|example| self.verify_with(rest, example)),
+            Augmentation::Inverse => task_inverse(Some(example)).is_some_and(|example| self.verify_with(rest, example)),
+            // The rule doesn't change, see `TaskDef::symmetries`
+            Augmentation::Colors | Augmentation::Shift => self.verify_with(rest, example),
+            // Swapping is its own inverse, and any color that explains the example will do
+            Augmentation::Background => (1..=colors())
+                .filter(|color| !self.fixed_colors.contains(color))
+                .any(|color| task_swap_background(Some(example.clone()), color).is_some_and(|example| self.verify_with(rest, example))),
+            Augmentation::Upscale => match (downscale(&example.input, 2), downscale(&example.output, 2)) {
+                (Some(input), Some(output)) => self.verify_with(rest, Example { input, output }),
+                _ => false,
+            },
+        }
+    }
+
+    /// What was sampled for the example, it's found from the example itself, like branches.
+    pub fn attributes(&self, example: &Example) -> Attributes {
+        Attributes {
+            size: example.input.len(),
+            colors: example.input.iter().chain(example.output.iter()).copied().filter(|&color| color != 0).collect(),
+            starts: example.input.objects().map(|object| object.start).collect(),
+            params: self.params.clone(),
+        }
+    }
+
+    /// Every example of given size, `None` if the task can't be enumerated.
+    ///
+    /// Inputs are listed by the enumerator of the task and outputs are found by its reference solver, then every outcome of each augmentation is added, so some examples may repeat.
+    pub fn enumerate(&self, size: usize) -> Option<Box<dyn Iterator<Item = Example> + '_>> {
+        let inputs = (self.enumerate?)(size, &self.params);
+        Some(Box::new(inputs.flat_map(move |input| {
+            let output = (self.solve)(&input, &self.params).expect("enumerated inputs can be generated, so they are solved");
+            self.augmentations.iter().fold(vec![Example { input, output }], |examples, augmentation| {
+                examples.into_iter().flat_map(|example| augmentation.outcomes(example, self.fixed_colors)).collect()
+            })
+        })))
+    }
+
+    /// The task has an enumerator, see [`TaskVariant::enumerate`].
+    pub fn is_enumerable(&self) -> bool {
+        self.enumerate.is_some()
+    }
+
+    /// The task has a constructive generator, see [`Sampling::Constructive`].
+    pub fn is_constructive(&self) -> bool {
+        self.construct.is_some()
+    }
+
+    /// Colors of each episode are permuted, see [`Augmentation::Colors`].
+    pub fn permutes_colors(&self) -> bool {
+        self.augmentations.contains(&Augmentation::Colors)
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    variants: Vec<TaskVariant>,
+    /// Symmetries of tasks which are added to the registry.
+    requested: Vec<Augmentation>,
+}
+
+impl Registry {
+    /// Registry which also has tasks with `requested` symmetries, see [`TaskDef::symmetries`].
+    pub fn with_augmentations(requested: &[Augmentation]) -> Registry {
+        Registry { variants: vec![], requested: requested.to_vec() }
+    }
+
+    /// Adds all combinations of parameters and augmentations of the task, names of the resulting tasks must be unique.
+    pub fn register(&mut self, def: TaskDef) -> Result<()> {
+        for augmentation in def.augmentations {
+            if !def.name.contains(augmentation.placeholder()) {
+                return Err(Error::Registry(format!("task `{}` has augmentation {augmentation:?}, but no `{}` in its name", def.name, augmentation.placeholder())));
+            }
+            if def.symmetries.contains(augmentation) {
+                return Err(Error::Registry(format!("task `{}` has augmentation {augmentation:?} both in the name and in symmetries", def.name)));
+            }
+        }
+
+        let mut combinations: Vec<(String, Vec<Augmentation>)> = vec![(def.name.to_string(), vec![])];
+        for &augmentation in def.augmentations {
+            combinations = combinations.into_iter()
+                .flat_map(|(name, applied)| {
+                    let [original, augmented] = augmentation.parts();
+                    let mut with = applied.clone();
+                    with.push(augmentation);
+                    [
+                        (name.replace(augmentation.placeholder(), &original), applied),
+                        (name.replace(augmentation.placeholder(), &augmented), with),
+                    ]
+                })
+                .collect();
+        }
+        for augmentation in Augmentation::ALL {
+            if !def.symmetries.contains(&augmentation) || !self.requested.contains(&augmentation) {
+                continue;
+            }
+            combinations = combinations.into_iter()
+                .flat_map(|(name, applied)| {
+                    let mut with = applied.clone();
+                    with.push(augmentation);
+                    [(name.clone(), applied), (name, with)]
+                })
+                .collect();
+        }
+        // Suffixes are in the same order whether augmentations are in the name or requested, so names are systematic. Direction is a word of the name instead.
+        for (name, applied) in &mut combinations {
+            for augmentation in Augmentation::ALL {
+                let direction = augmentation == Augmentation::Mirror && def.augmentations.contains(&augmentation);
+                if applied.contains(&augmentation) && !direction {
+                    name.push('_');
+                    name.push_str(augmentation.suffix());
+                }
+            }
+        }
+
+        for params in &def.params {
+            for (name, applied) in &combinations {
+                let mut name = name.clone();
+                for (key, value) in &params.0 {
+                    name = name.replace(&format!("{{{key}}}"), &value.to_string());
+                }
+                if name.contains('{') {
+                    return Err(Error::Registry(format!("task `{name}` has placeholder without value")));
+                }
+                if self.get(&name).is_some() {
+                    return Err(Error::Registry(format!("task `{name}` is registered twice")));
+                }
+                self.variants.push(TaskVariant {
+                    name,
+                    description: def.description,
+                    params: params.clone(),
+                    min_size: (def.min_size)(params),
+                    same_size: def.same_size,
+                    // Background is swapped with a color which is not fixed
+                    min_colors: if applied.contains(&Augmentation::Background) { def.min_colors.max(def.fixed_colors.len() as u8 + 1) } else { def.min_colors },
+                    fixed_colors: def.fixed_colors,
+                    augmentations: applied.clone(),
+                    branches: def.branches.filter(|_| applied.iter().all(|augmentation| augmentation.keeps_branches())),
+                    generate: def.generate,
+                    construct: def.construct,
+                    enumerate: def.enumerate,
+                    solve: def.solve,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn variants(&self) -> &[TaskVariant] {
+        &self.variants
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TaskVariant> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+}
+
+/// All tasks of this crate.
+pub fn registry() -> Registry {
+    registry_with(&[])
+}
+
+/// All tasks of this crate, and their variants with `requested` symmetries.
+pub fn registry_with(requested: &[Augmentation]) -> Registry {
+    use Augmentation::*;
+
+    let defs = vec![
+        TaskDef {
+            name: "block_touch_dot_{pixels}_pix",
+            description: "Block moves N pixels towards the dot of color 2, but not further than touching it.",
+            params: param_grid("pixels", 1..=4),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[2],
+            augmentations: &[],
+            symmetries: &[Colors, Background],
+            branches: Some(Branches { names: &["left", "right"], classify: |example| branch_block_side_of_dot(example, 2) }),
+            generate: |size, p, rng| task_block_touch_dot_n_pix(size, p.get("pixels"), rng),
+            construct: Some(|size, p, rng| construct_block_touch_dot_n_pix(size, p.get("pixels"), rng)),
+            enumerate: None,
+            solve: |input, p| solve_block_touch_dot_n_pix(input, p.get("pixels")),
+        },
+        TaskDef {
+            name: "move_{pixels}pix_solid_{dir}",
+            description: "Solid block moves N pixels.",
+            params: param_grid("pixels", 1..=4),
+            min_size: |p| p.get("pixels") + 2,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Inverse, Colors, Background],
+            branches: None,
+            generate: |size, p, rng| task_move_n_pix(size, p.get("pixels"), true, rng),
+            construct: None,
+            enumerate: Some(|size, p| Box::new(enumerate_move_n_pix(size, p.get("pixels"), true))),
+            solve: |input, p| solve_move_n_pix(input, p.get("pixels")),
+        },
+        TaskDef {
+            name: "move_{pixels}pix_colorful_{dir}",
+            description: "Block of random colors moves N pixels.",
+            params: param_grid("pixels", 1..=4),
+            min_size: |p| p.get("pixels") + 2,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Inverse, Colors, Background],
+            branches: None,
+            generate: |size, p, rng| task_move_n_pix(size, p.get("pixels"), false, rng),
+            construct: None,
+            enumerate: Some(|size, p| Box::new(enumerate_move_n_pix(size, p.get("pixels"), false))),
+            solve: |input, p| solve_move_n_pix(input, p.get("pixels")),
+        },
+        TaskDef {
+            name: "move_{pixels}pix_solid_{dir}_wrapped",
+            description: "Solid block moves N pixels, wrapping around the border.",
+            params: param_grid("pixels", 1..=4),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Inverse, Colors, Background],
+            branches: None,
+            generate: |size, p, rng| task_move_n_pix_wrapped(size, p.get("pixels"), true, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_move_n_pix_wrapped(size, true))),
+            solve: |input, p| solve_move_n_pix_wrapped(input, p.get("pixels")),
+        },
+        TaskDef {
+            name: "move_{pixels}pix_colorful_{dir}_wrapped",
+            description: "Block of random colors moves N pixels, wrapping around the border.",
+            params: param_grid("pixels", 1..=4),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Inverse, Colors, Background],
+            branches: None,
+            generate: |size, p, rng| task_move_n_pix_wrapped(size, p.get("pixels"), false, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_move_n_pix_wrapped(size, false))),
+            solve: |input, p| solve_move_n_pix_wrapped(input, p.get("pixels")),
+        },
+        TaskDef {
+            name: "gravity_{dir}",
+            description: "All pixels fall to one side.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_gravity(size, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_sparse_field(size))),
+            solve: |input, _| solve_gravity(input),
+        },
+        TaskDef {
+            name: "gravity_antigravity_{dir}",
+            description: "Pixels of color 1 fall to one side, pixels of color 2 to the other.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[1, 2],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_gravity_antigravity(size, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_sparse_field_two_colors(size))),
+            solve: |input, _| solve_gravity_antigravity(input),
+        },
+        TaskDef {
+            name: "gravity_counting_{dir}",
+            description: "All pixels fall to one side and become color 1.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[1],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_gravity_counting(size, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_sparse_field(size))),
+            solve: |input, _| solve_gravity_counting(input),
+        },
+        TaskDef {
+            name: "gravity_one_step_{dir}",
+            description: "Every pixel falls at most one pixel.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_gravity_one_step(size, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_sparse_field(size))),
+            solve: |input, _| solve_gravity_one_step(input),
+        },
+        TaskDef {
+            name: "move_block_by_own_size_{dir}",
+            description: "Solid block moves by its own size.",
+            params: no_params(),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Inverse, Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_move_block_by_own_size(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_move_block_by_own_size(input),
+        },
+        TaskDef {
+            name: "gravity_weighted_colors_{dir}",
+            description: "Pixels fall to one side, color 2 is heavier than color 1.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[1, 2],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_gravity_weighted_colors(size, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_sparse_field_two_colors(size))),
+            solve: |input, _| solve_gravity_weighted_colors(input),
+        },
+        TaskDef {
+            name: "color_left_half_blocks_{dir}",
+            description: "Half of each block of color 2 is painted with color 8.",
+            params: no_params(),
+            min_size: |_| 6,
+            same_size: true,
+            min_colors: 8,
+            fixed_colors: &[2, 8],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_color_left_half_blocks(size, rng),
+            construct: Some(|size, _, rng| construct_color_left_half_blocks(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_color_left_half_blocks(input),
+        },
+        TaskDef {
+            name: "recolor_blocks_from_palette_{dir}",
+            description: "Blocks of color 5 are painted with colors of the palette in the corner.",
+            params: no_params(),
+            min_size: |_| 5,
+            same_size: true,
+            min_colors: 5,
+            fixed_colors: &[5],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_recolor_blocks_from_palette(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_recolor_blocks_from_palette(input),
+        },
+        TaskDef {
+            name: "sort_complete_sequence_{dir}",
+            description: "Blocks of all sizes from 1 to maximum are sorted by size.",
+            params: no_params(),
+            // smaller fields have only one block, so there is nothing to sort
+            min_size: |_| 5,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_sort_complete_sequence(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_sort_blocks_by_size(input),
+        },
+        TaskDef {
+            name: "sort_blocks_by_size_{dir}",
+            description: "Blocks are sorted by size with gap 1.",
+            params: no_params(),
+            min_size: |_| 4,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Mirror],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_sort_blocks_by_size(size, rng),
+            construct: Some(|size, _, rng| construct_sort_blocks_by_size(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_sort_blocks_by_size(input),
+        },
+        TaskDef {
+            name: "two_points_and_fill{inv}",
+            description: "Space between two points of the same color is filled with that color.",
+            params: no_params(),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[Inverse],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_two_points_and_fill(size, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_two_points_and_fill(size))),
+            solve: |input, _| solve_two_points_and_fill(input),
+        },
+        TaskDef {
+            name: "block_touch_dot",
+            description: "Block moves until it touches the dot of color 1.",
+            params: no_params(),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[1],
+            augmentations: &[],
+            symmetries: &[Colors, Background, Upscale],
+            branches: Some(Branches { names: &["left", "right"], classify: |example| branch_block_side_of_dot(example, 1) }),
+            generate: |size, _, rng| task_block_touch_dot(size, rng),
+            construct: Some(|size, _, rng| construct_block_touch_dot(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_block_touch_dot(input),
+        },
+        TaskDef {
+            name: "block_scale_to_dot",
+            description: "Block is stretched until it reaches the dot of color 2.",
+            params: no_params(),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[2],
+            augmentations: &[],
+            symmetries: &[Colors, Background, Upscale],
+            branches: Some(Branches { names: &["left", "right"], classify: |example| branch_block_side_of_dot(example, 2) }),
+            generate: |size, _, rng| task_block_scale_to_dot(size, rng),
+            construct: Some(|size, _, rng| construct_block_scale_to_dot(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_block_scale_to_dot(input),
+        },
+        TaskDef {
+            name: "reflect_block_with_border_pixel",
+            description: "Solid block with a pixel of other color at its border is reflected.",
+            params: no_params(),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_reflect_block_with_border_pixel(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_reflect_block(input),
+        },
+        TaskDef {
+            name: "reflect_block_random",
+            description: "Block of random colors with a pixel at its border is reflected.",
+            params: no_params(),
+            min_size: |_| 2,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_reflect_block_with_border_pixel_random(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_reflect_block(input),
+        },
+        TaskDef {
+            name: "reflect_block_around_dot",
+            description: "Block is reflected around the dot of color 2.",
+            params: no_params(),
+            min_size: |_| 3,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[2],
+            augmentations: &[],
+            symmetries: &[Inverse, Colors, Background, Upscale],
+            branches: Some(Branches { names: &["left", "right"], classify: |example| branch_block_side_of_dot(example, 2) }),
+            generate: |size, _, rng| task_reflect_block_around_dot(size, rng),
+            construct: Some(|size, _, rng| construct_reflect_block_around_dot(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_reflect_block_around_dot(input),
+        },
+        TaskDef {
+            name: "block_and_noise_remove",
+            description: "Noise pixels of the same color as the block are removed.",
+            params: no_params(),
+            min_size: |_| 4,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_block_and_noise_remove(size, rng),
+            construct: Some(|size, _, rng| construct_block_and_noise_remove(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_block_and_noise_remove(input),
+        },
+        TaskDef {
+            name: "block_and_noise_remove_inside",
+            description: "Noise pixels inside the block are painted with color of the block.",
+            params: no_params(),
+            min_size: |_| 7,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_block_and_noise_remove_inside(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_block_and_noise_remove_inside(input),
+        },
+        TaskDef {
+            name: "copy_block_to_dots",
+            description: "Block at the start is copied to the center of each dot.",
+            params: no_params(),
+            min_size: |_| 8,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Mirror, Colors, Background],
+            branches: Some(Branches { names: &["block_3", "block_5"], classify: branch_copied_block_size }),
+            generate: |size, _, rng| task_copy_block_to_dots(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_copy_block_to_dots(input, false),
+        },
+        TaskDef {
+            name: "copy_block_to_dots_colors",
+            description: "Block at the start is copied to the center of each dot with color of the dot.",
+            params: no_params(),
+            min_size: |_| 9,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Mirror, Colors, Background],
+            branches: Some(Branches { names: &["block_3", "block_5"], classify: branch_copied_block_size }),
+            generate: |size, _, rng| task_copy_block_to_dots_colors(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_copy_block_to_dots(input, true),
+        },
+        TaskDef {
+            name: "paint_biggest_block",
+            description: "The biggest block is painted with color 1.",
+            params: no_params(),
+            min_size: |_| 6,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[1],
+            augmentations: &[],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_paint_biggest_block(size, rng),
+            construct: Some(|size, _, rng| construct_paint_biggest_block(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_paint_biggest_block(input),
+        },
+        TaskDef {
+            name: "recolor_blocks_by_size",
+            description: "Of two blocks of color 3, the bigger becomes color 1 and the smaller color 2.",
+            params: no_params(),
+            min_size: |_| 6,
+            same_size: true,
+            min_colors: 3,
+            fixed_colors: &[1, 2, 3],
+            augmentations: &[],
+            symmetries: &[Colors, Background, Upscale],
+            branches: Some(Branches { names: &["bigger_first", "bigger_last"], classify: branch_bigger_block_first }),
+            generate: |size, _, rng| task_recolor_blocks_by_size(size, rng),
+            construct: Some(|size, _, rng| construct_recolor_blocks_by_size(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_recolor_blocks_by_size(input),
+        },
+        TaskDef {
+            name: "change_to_five",
+            description: "Every non-zero color becomes color 5.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: true,
+            min_colors: 5,
+            fixed_colors: &[5],
+            augmentations: &[],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_change_to_five(size, rng),
+            construct: None,
+            enumerate: Some(|size, _| Box::new(enumerate_sparse_field(size))),
+            solve: |input, _| solve_change_to_five(input),
+        },
+        TaskDef {
+            name: "duplicate_block_from_seeds",
+            description: "Block of color 1 is repeated from each seed pixel to the border with the seed color.",
+            params: no_params(),
+            min_size: |_| 6,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[1],
+            augmentations: &[],
+            symmetries: &[Colors, Background],
+            branches: Some(Branches { names: &["left", "right", "both"], classify: branch_seed_sides }),
+            generate: |size, _, rng| task_duplicate_block_from_seeds(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_duplicate_block_from_seeds(input),
+        },
+        TaskDef {
+            name: "fill_from_pixel",
+            description: "Pixel next to the block fills the space to the border on its side.",
+            params: no_params(),
+            min_size: |_| 6,
+            same_size: true,
+            min_colors: 2,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Mirror, Colors, Background, Upscale],
+            branches: Some(Branches { names: &["left", "right"], classify: branch_fill_side }),
+            generate: |size, _, rng| task_fill_from_pixel(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_fill_from_pixel(input),
+        },
+        TaskDef {
+            name: "mark_size_two_blocks",
+            description: "Blocks of size 2 are surrounded by pixels of color 3.",
+            params: no_params(),
+            min_size: |_| 6,
+            same_size: true,
+            min_colors: 3,
+            fixed_colors: &[1, 3],
+            augmentations: &[],
+            symmetries: &[Mirror, Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_mark_size_two_blocks(size, rng),
+            construct: Some(|size, _, rng| construct_mark_size_two_blocks(size, rng)),
+            enumerate: None,
+            solve: |input, _| solve_mark_size_two_blocks(input),
+        },
+        TaskDef {
+            name: "fill_until_collision",
+            description: "Pixels fill empty space towards the pixel of color 5 until they reach another pixel.",
+            params: no_params(),
+            min_size: |_| 4,
+            same_size: true,
+            min_colors: 5,
+            fixed_colors: &[5],
+            augmentations: &[],
+            symmetries: &[Colors, Background, Upscale],
+            branches: Some(Branches { names: &["left", "right"], classify: branch_marker_side }),
+            generate: |size, _, rng| task_fill_until_collision(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_fill_until_collision(input),
+        },
+        TaskDef {
+            name: "repeat_pattern_full",
+            description: "Pattern repeated two times is repeated until the end of the field.",
+            params: no_params(),
+            min_size: |_| 5,
+            same_size: true,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Mirror, Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_repeat_pattern_full(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_repeat_pattern_full(input),
+        },
+        TaskDef {
+            name: "crop_block",
+            description: "Block of random colors is cropped, output is only the block.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: false,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_crop_block(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_crop_block(input),
+        },
+        TaskDef {
+            name: "count_pixels",
+            description: "Scattered pixels of one color are counted, output is a block of that size.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: false,
+            min_colors: 1,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Colors, Background, Upscale],
+            branches: None,
+            generate: |size, _, rng| task_count_pixels(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_count_pixels(input),
+        },
+        TaskDef {
+            name: "compress_runs",
+            description: "Each solid block becomes one pixel of its color.",
+            params: no_params(),
+            min_size: |_| 1,
+            same_size: false,
+            min_colors: 2,
+            fixed_colors: &[],
+            augmentations: &[],
+            symmetries: &[Colors, Background],
+            branches: None,
+            generate: |size, _, rng| task_compress_runs(size, rng),
+            construct: None,
+            enumerate: None,
+            solve: |input, _| solve_compress_runs(input),
+        },
+    ];
+
+    let mut registry = Registry::with_augmentations(requested);
+    for def in defs {
+        registry.register(def).unwrap_or_else(|err| panic!("tasks of this crate are invalid: {err}"));
+    }
+    registry
+}
@@ -0,0 +1,403 @@
+// Reference solvers of tasks, they find the output from the input alone
+
+use crate::field::*;
+use crate::grid::{Grid, Run};
+
+// Solvers return `None` when the input can't be generated by the task, so they can't be fooled by inputs of other tasks.
+
+/// Position of the first and the last non-background cells.
+fn span(input: &Grid) -> Option<(usize, usize)> {
+    let first = input.iter().position(|&c| c != 0)?;
+    let last = input.iter().rposition(|&c| c != 0)?;
+    Some((first, last))
+}
+
+/// The only object of the input.
+fn single_object(input: &Grid) -> Option<Run> {
+    let mut objects = input.objects();
+    let object = objects.next()?;
+    objects.next().is_none().then_some(object)
+}
+
+/// Position of the only dot of `dot_color` and the only block of other color, the dot may touch the block.
+fn dot_and_block(input: &Grid, dot_color: u8) -> Option<(usize, Run)> {
+    let (dots, blocks): (Vec<_>, Vec<_>) = input.objects().partition(|run| run.color.value() == dot_color);
+    match (&dots[..], &blocks[..]) {
+        ([dot], [block]) if dot.len == 1 => Some((dot.start, *block)),
+        _ => None,
+    }
+}
+
+/// Every cell moves `move_pix` pixels to the right, nothing crosses the border.
+pub fn solve_move_n_pix(input: &Grid, move_pix: usize) -> Option<Grid> {
+    let (_, last) = span(input)?;
+    if last + move_pix >= input.len() {
+        return None;
+    }
+    write_block(move_pix, &input[..input.len() - move_pix], gen_field(input.len()))
+}
+
+pub fn solve_move_n_pix_wrapped(input: &Grid, move_pix: usize) -> Option<Grid> {
+    span(input)?;
+    let mut output = input.clone();
+    output.rotate_right(move_pix % input.len());
+    Some(output)
+}
+
+/// All pixels fall to the right.
+pub fn solve_gravity(input: &Grid) -> Option<Grid> {
+    let pixels = remove_color(0, input.clone());
+    write_block(input.len() - pixels.len(), &pixels, gen_field(input.len()))
+}
+
+/// All pixels fall to the left and become color 1.
+pub fn solve_gravity_counting(input: &Grid) -> Option<Grid> {
+    let count = input.iter().filter(|&&c| c != 0).count();
+    write_block(0, &gen_field_color(count, 1)?, gen_field(input.len()))
+}
+
+/// Pixels of color 1 fall to the left, pixels of color 2 to the right.
+pub fn solve_gravity_antigravity(input: &Grid) -> Option<Grid> {
+    if input.iter().any(|&c| c > 2) {
+        return None;
+    }
+    let ones = input.iter().filter(|&&c| c == 1).count();
+    let twos = input.iter().filter(|&&c| c == 2).count();
+    let output = write_block(input.len() - twos, &gen_field_color(twos, 2)?, gen_field(input.len()))?;
+    write_block(0, &gen_field_color(ones, 1)?, output)
+}
+
+/// Pixels are moved one step to the left from left to right, so a pixel can move into the place left by its neighbour.
+pub fn solve_gravity_one_step(input: &Grid) -> Option<Grid> {
+    let mut output = input.clone();
+    for i in 1..output.len() {
+        if output[i] != 0 && output[i - 1] == 0 {
+            output.set(i - 1, output[i]).ok()?;
+            output.set(i, 0).ok()?;
+        }
+    }
+    Some(output)
+}
+
+/// Solid block moves to the right by its own size.
+pub fn solve_move_block_by_own_size(input: &Grid) -> Option<Grid> {
+    let block = single_object(input)?;
+    write_block(block.start + block.len, &gen_field_color(block.len, block.color.value())?, gen_field(input.len()))
+}
+
+/// Pixels fall to the left, pixels of color 2 before pixels of color 1.
+pub fn solve_gravity_weighted_colors(input: &Grid) -> Option<Grid> {
+    if input.iter().any(|&c| c > 2) {
+        return None;
+    }
+    let ones = input.iter().filter(|&&c| c == 1).count();
+    let twos = input.iter().filter(|&&c| c == 2).count();
+    let output = write_block(0, &gen_field_color(twos, 2)?, gen_field(input.len()))?;
+    write_block(twos, &gen_field_color(ones, 1)?, output)
+}
+
+/// Left half of every block of color 2 becomes color 8.
+pub fn solve_color_left_half_blocks(input: &Grid) -> Option<Grid> {
+    let mut output = input.clone();
+    for block in input.objects() {
+        if block.color.value() != 2 {
+            return None;
+        }
+        output.fill(block.start..block.start + block.len / 2, 8).ok()?;
+    }
+    Some(output)
+}
+
+/// Blocks of color 5 are painted with colors of the palette before the first background cell, in order.
+pub fn solve_recolor_blocks_from_palette(input: &Grid) -> Option<Grid> {
+    let palette_size = input.iter().position(|&c| c == 0)?;
+    let palette = &input[..palette_size];
+    let mut output = input.clone();
+    let mut blocks = 0;
+    for block in input.objects().filter(|run| run.start > palette_size) {
+        if block.color.value() != 5 {
+            return None;
+        }
+        output.fill(block.start..block.end(), *palette.get(blocks)?).ok()?;
+        blocks += 1;
+    }
+    (blocks == palette_size).then_some(output)
+}
+
+/// Blocks are sorted by size from the left border with gap 1.
+pub fn solve_sort_blocks_by_size(input: &Grid) -> Option<Grid> {
+    let mut blocks = input.objects().collect::<Vec<_>>();
+    let color = blocks.first()?.color.value();
+    if blocks.iter().any(|block| block.color.value() != color) {
+        return None;
+    }
+    blocks.sort_by_key(|block| block.len);
+    let mut output = gen_field(input.len());
+    let mut pos = 0;
+    for block in blocks {
+        output = write_block(pos, &gen_field_color(block.len, color)?, output)?;
+        pos += block.len + 1;
+    }
+    Some(output)
+}
+
+/// Space between the two points is filled with their color.
+pub fn solve_two_points_and_fill(input: &Grid) -> Option<Grid> {
+    let (first, last) = span(input)?;
+    let color = input[first];
+    if first == last || input[last] != color || input.iter().filter(|&&c| c != 0).count() != 2 {
+        return None;
+    }
+    let mut output = input.clone();
+    output.fill(first..=last, color).ok()?;
+    Some(output)
+}
+
+/// Block of any color moves until it touches the dot of color 1.
+pub fn solve_block_touch_dot(input: &Grid) -> Option<Grid> {
+    let (dot, block) = dot_and_block(input, 1)?;
+    let start = if block.start < dot { dot - block.len } else { dot + 1 };
+    let mut output = write_block(start, &gen_field_color(block.len, block.color.value())?, gen_field(input.len()))?;
+    output.set(dot, 1).ok()?;
+    Some(output)
+}
+
+/// Block moves at most `move_pix` pixels towards the dot of color 2, but not further than touching it.
+pub fn solve_block_touch_dot_n_pix(input: &Grid, move_pix: usize) -> Option<Grid> {
+    let (dot, block) = dot_and_block(input, 2)?;
+    let start = if block.start < dot {
+        block.start + (dot - block.end()).min(move_pix)
+    } else {
+        block.start - (block.start - dot - 1).min(move_pix)
+    };
+    let mut output = write_block(start, &gen_field_color(block.len, block.color.value())?, gen_field(input.len()))?;
+    output.set(dot, 2).ok()?;
+    Some(output)
+}
+
+/// Near end of the block is moved to touch the dot of color 2, the far end stays in place.
+pub fn solve_block_scale_to_dot(input: &Grid) -> Option<Grid> {
+    let (dot, block) = dot_and_block(input, 2)?;
+    let mut output = input.clone();
+    if block.start < dot {
+        output.fill(block.start..dot, block.color.value()).ok()?;
+    } else {
+        output.fill(dot + 1..block.end(), block.color.value()).ok()?;
+    }
+    Some(output)
+}
+
+/// The block, which may be of many colors, is reflected in place.
+pub fn solve_reflect_block(input: &Grid) -> Option<Grid> {
+    let (first, last) = span(input)?;
+    if input[first..=last].contains(&0) {
+        return None;
+    }
+    let mut output = input.clone();
+    output.reverse(first..=last).ok()?;
+    Some(output)
+}
+
+/// Block is reflected around the dot of color 2.
+pub fn solve_reflect_block_around_dot(input: &Grid) -> Option<Grid> {
+    let (dot, block) = dot_and_block(input, 2)?;
+    let start = (2 * dot).checked_sub(block.end() - 1)?;
+    let mut output = write_block(start, &gen_field_color(block.len, block.color.value())?, gen_field(input.len()))?;
+    output.set(dot, 2).ok()?;
+    Some(output)
+}
+
+/// Everything except the longest block is removed.
+pub fn solve_block_and_noise_remove(input: &Grid) -> Option<Grid> {
+    let block = input.objects().max_by_key(|run| run.len)?;
+    let mut output = gen_field(input.len());
+    output.fill(block.start..block.end(), block.color.value()).ok()?;
+    Some(output)
+}
+
+/// Noise inside the block is painted with the color of the block, it's the most common color there.
+pub fn solve_block_and_noise_remove_inside(input: &Grid) -> Option<Grid> {
+    let (first, last) = span(input)?;
+    let block = &input[first..=last];
+    if block.contains(&0) {
+        return None;
+    }
+    let color = *block.iter().max_by_key(|&&c| block.iter().filter(|&&other| other == c).count())?;
+    let mut output = input.clone();
+    output.fill(first..=last, color).ok()?;
+    Some(output)
+}
+
+/// Block at the start is copied to be centered at each dot, it gets the color of the dot if `dot_colors` is set.
+pub fn solve_copy_block_to_dots(input: &Grid, dot_colors: bool) -> Option<Grid> {
+    let mut objects = input.objects();
+    let block = objects.next()?;
+    let mut output = input.clone();
+    let mut dots = 0;
+    for dot in objects {
+        if dot.len != 1 || (!dot_colors && dot.color != block.color) {
+            return None;
+        }
+        let start = dot.start.checked_sub(block.len / 2)?;
+        output = write_block(start, &gen_field_color(block.len, dot.color.value())?, output)?;
+        dots += 1;
+    }
+    (dots > 0).then_some(output)
+}
+
+/// The only biggest block is painted with color 1.
+pub fn solve_paint_biggest_block(input: &Grid) -> Option<Grid> {
+    let blocks = input.objects().collect::<Vec<_>>();
+    let biggest = blocks.iter().max_by_key(|block| block.len)?;
+    if blocks.iter().filter(|block| block.len == biggest.len).count() > 1 {
+        return None;
+    }
+    let mut output = input.clone();
+    output.fill(biggest.start..biggest.end(), 1).ok()?;
+    Some(output)
+}
+
+/// Bigger of two blocks is painted with color 1, smaller with color 2.
+pub fn solve_recolor_blocks_by_size(input: &Grid) -> Option<Grid> {
+    let blocks = input.objects().collect::<Vec<_>>();
+    let [first, second] = blocks[..] else {
+        return None;
+    };
+    if first.len == second.len {
+        return None;
+    }
+    let (bigger, smaller) = if first.len > second.len { (first, second) } else { (second, first) };
+    let mut output = input.clone();
+    output.fill(bigger.start..bigger.end(), 1).ok()?;
+    output.fill(smaller.start..smaller.end(), 2).ok()?;
+    Some(output)
+}
+
+/// Every non-background color becomes color 5.
+pub fn solve_change_to_five(input: &Grid) -> Option<Grid> {
+    Grid::from_cells(input.iter().map(|&c| if c != 0 { 5 } else { 0 }).collect()).ok()
+}
+
+/// Block of color 1 is repeated with gap 1 from each seed to the border, in the color of the seed.
+pub fn solve_duplicate_block_from_seeds(input: &Grid) -> Option<Grid> {
+    let size = input.len();
+    let block = input.objects().filter(|run| run.color.value() == 1).max_by_key(|run| run.len)?;
+    let mut output = input.clone();
+    if block.start >= 2 && input[block.start - 2] != 0 {
+        let color = input[block.start - 2];
+        let mut end = block.start - 2;
+        loop {
+            let start = (end + 1).saturating_sub(block.len);
+            output.fill(start..=end, color).ok()?;
+            if start < 2 {
+                break;
+            }
+            end = start - 2;
+        }
+    }
+    if block.end() + 1 < size && input[block.end() + 1] != 0 {
+        let color = input[block.end() + 1];
+        let mut start = block.end() + 1;
+        while start < size {
+            let end = (start + block.len).min(size);
+            output.fill(start..end, color).ok()?;
+            start += block.len + 1;
+        }
+    }
+    Some(output)
+}
+
+/// Seed pixel next to the block fills everything on its side with its color.
+pub fn solve_fill_from_pixel(input: &Grid) -> Option<Grid> {
+    let objects = input.objects().collect::<Vec<_>>();
+    let [first, second] = objects[..] else {
+        return None;
+    };
+    let mut output = input.clone();
+    if first.len == 1 && first.end() == second.start {
+        output.fill(..first.end(), first.color.value()).ok()?;
+    } else if second.len == 1 && first.end() == second.start {
+        output.fill(second.start.., second.color.value()).ok()?;
+    } else {
+        return None;
+    }
+    Some(output)
+}
+
+/// Every block of color 1 and size 2 gets pixels of color 3 at its sides.
+pub fn solve_mark_size_two_blocks(input: &Grid) -> Option<Grid> {
+    let mut output = input.clone();
+    for block in input.objects() {
+        if block.color.value() != 1 {
+            return None;
+        }
+        if block.len == 2 {
+            if block.start > 0 {
+                output.set(block.start - 1, 3).ok()?;
+            }
+            if block.end() < input.len() {
+                output.set(block.end(), 3).ok()?;
+            }
+        }
+    }
+    Some(output)
+}
+
+/// Each pixel fills empty space towards the marker of color 5 at the border, until it reaches another pixel.
+pub fn solve_fill_until_collision(input: &Grid) -> Option<Grid> {
+    let size = input.len();
+    let mut output = input.clone();
+    if input.first() == Some(&5) {
+        let mut prev = 0;
+        for pos in (1..size).filter(|&pos| input[pos] != 0) {
+            output.fill(prev + 1..pos, input[pos]).ok()?;
+            prev = pos;
+        }
+    } else if input.last() == Some(&5) {
+        let mut prev = size - 1;
+        for pos in (0..size - 1).rev().filter(|&pos| input[pos] != 0) {
+            output.fill(pos + 1..prev, input[pos]).ok()?;
+            prev = pos;
+        }
+    } else {
+        return None;
+    }
+    Some(output)
+}
+
+/// Pattern which is repeated two times at the start is repeated over the whole field.
+pub fn solve_repeat_pattern_full(input: &Grid) -> Option<Grid> {
+    let filled = input.iter().position(|&c| c == 0)?;
+    if filled == 0 || !filled.is_multiple_of(2) || input[filled..].iter().any(|&c| c != 0) {
+        return None;
+    }
+    let pattern = &input[..filled / 2];
+    if pattern != &input[filled / 2..filled] {
+        return None;
+    }
+    Grid::from_cells(pattern.iter().copied().cycle().take(input.len()).collect()).ok()
+}
+
+/// The block without empty space around it.
+pub fn solve_crop_block(input: &Grid) -> Option<Grid> {
+    let (first, last) = span(input)?;
+    Grid::from_cells(input[first..=last].to_vec()).ok()
+}
+
+/// Solid block with one pixel for each pixel of the input.
+pub fn solve_count_pixels(input: &Grid) -> Option<Grid> {
+    let (first, _) = span(input)?;
+    let color = input[first];
+    if input.iter().any(|&c| c != 0 && c != color) {
+        return None;
+    }
+    gen_field_color(input.iter().filter(|&&c| c != 0).count(), color)
+}
+
+/// One pixel for each run.
+pub fn solve_compress_runs(input: &Grid) -> Option<Grid> {
+    if input.contains(&0) {
+        return None;
+    }
+    Grid::from_cells(input.runs().map(|run| run.color.value()).collect()).ok()
+}
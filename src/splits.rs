@@ -0,0 +1,212 @@
+// Dataset splits, each with its own field sizes and directory, and the manifest describing them
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::dataset::{generate_all, save_json_to_file, stable_hash, GenerateConfig, GenerationStats, SizeRange};
+use crate::registry::{registry, Attributes, Params};
+use crate::{Error, Result};
+
+/// Attribute of examples which is held out: examples with it are only in held out splits, see [`Split::held_out`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Holdout {
+    /// The color appears in the example, see [`Attributes::colors`].
+    Color(u8),
+    /// Some object of the input starts in `from..to`, they are fractions of the size.
+    Start { from: f64, to: f64 },
+    /// Task parameter has this value, like `pixels=3` for `move_3pix_solid_right`.
+    Param { key: String, value: usize },
+}
+
+impl Holdout {
+    /// Colors which are held out, the permutation of colors keeps them in place, see [`crate::dataset::to_arc_tasks`].
+    pub fn colors(holdouts: &[Holdout]) -> Vec<u8> {
+        holdouts.iter().filter_map(|holdout| match holdout {
+            Holdout::Color(color) => Some(*color),
+            _ => None,
+        }).collect()
+    }
+
+    pub fn matches(&self, attributes: &Attributes) -> bool {
+        match self {
+            Holdout::Color(color) => attributes.colors.contains(color),
+            Holdout::Start { from, to } => attributes.starts.iter().any(|&start| {
+                let start = start as f64 / attributes.size as f64;
+                *from <= start && start < *to
+            }),
+            Holdout::Param { .. } => self.matches_params(&attributes.params),
+        }
+    }
+
+    /// All examples of the task with these parameters have the attribute.
+    pub fn matches_params(&self, params: &Params) -> bool {
+        match self {
+            Holdout::Param { key, value } => params.0.iter().any(|(k, v)| k == key && v == value),
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Holdout {
+    type Err = String;
+
+    /// Parses `color=7`, `start=2/3..1` or `KEY=VALUE` for a task parameter, the key must be a parameter of some task.
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (key, value) = s.split_once('=').ok_or_else(|| format!("expected KEY=VALUE, got `{s}`"))?;
+        let fraction = |s: &str| -> Result<f64, String> {
+            let parse = |s: &str| s.trim().parse::<f64>().map_err(|err| format!("invalid fraction `{s}`: {err}"));
+            match s.split_once('/') {
+                Some((numerator, denominator)) => Ok(parse(numerator)? / parse(denominator)?),
+                None => parse(s),
+            }
+        };
+        match key {
+            "color" => value.parse().map(Holdout::Color).map_err(|err| format!("invalid color `{value}`: {err}")),
+            "start" => {
+                let (from, to) = value.split_once("..").ok_or_else(|| format!("expected FROM..TO, got `{value}`"))?;
+                let (from, to) = (fraction(from)?, fraction(to)?);
+                if !(0.0 <= from && from < to) {
+                    return Err(format!("empty range of positions `{value}`"));
+                }
+                Ok(Holdout::Start { from, to })
+            }
+            _ => {
+                let keys = registry().variants().iter()
+                    .flat_map(|variant| variant.params.0.iter().map(|(key, _)| *key))
+                    .collect::<BTreeSet<_>>();
+                if !keys.contains(key) {
+                    let keys = keys.iter().map(|key| format!("`{key}`")).collect::<Vec<_>>().join(", ");
+                    return Err(format!("unknown key `{key}`, expected `color`, `start` or a task parameter: {keys}"));
+                }
+                Ok(Holdout::Param { key: key.to_string(), value: value.parse().map_err(|err| format!("invalid value `{value}`: {err}"))? })
+            }
+        }
+    }
+}
+
+impl fmt::Display for Holdout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Holdout::Color(color) => write!(f, "color={color}"),
+            Holdout::Start { from, to } => write!(f, "start={from}..{to}"),
+            Holdout::Param { key, value } => write!(f, "{key}={value}"),
+        }
+    }
+}
+
+/// Part of the dataset with its own field sizes, it's saved to the directory with its name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Split {
+    pub name: String,
+    pub sizes: SizeRange,
+    /// The split has only examples with held out attributes, other splits have only examples without them.
+    pub held_out: bool,
+}
+
+impl FromStr for Split {
+    type Err = String;
+
+    /// Parses `train=5..=12`, or `test=5..=12:held-out` for a held out split.
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (name, sizes) = s.split_once('=').ok_or_else(|| format!("expected NAME=SIZE, got `{s}`"))?;
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(format!("split name `{name}` is not a directory name"));
+        }
+        let (sizes, held_out) = match sizes.strip_suffix(":held-out") {
+            Some(sizes) => (sizes, true),
+            None => (sizes, false),
+        };
+        Ok(Split { name: name.to_string(), sizes: sizes.parse()?, held_out })
+    }
+}
+
+/// Seed of all tasks of the split, so splits with the same sizes still have different examples.
+pub fn split_seed(seed: u64, split: &str) -> u64 {
+    stable_hash(&[&seed.to_le_bytes(), b"split", split.as_bytes()])
+}
+
+/// Statistics of one saved task of a split.
+#[derive(Clone, Debug, Serialize)]
+pub struct TaskManifest {
+    pub task: String,
+    #[serde(flatten)]
+    pub stats: GenerationStats,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SplitManifest {
+    pub name: String,
+    pub sizes: SizeRange,
+    pub held_out: bool,
+    pub seed: u64,
+    /// Tasks saved to the split, tasks which don't fit its sizes are missing.
+    pub tasks: Vec<TaskManifest>,
+}
+
+/// Description of all splits, it's saved as `manifest.json` next to their directories.
+#[derive(Clone, Debug, Serialize)]
+pub struct Manifest {
+    pub seed: u64,
+    /// Maximum number of examples in each task.
+    pub examples: usize,
+    pub train_pairs: usize,
+    pub colors: u8,
+    /// Held out attributes, like `color=7`, see [`Holdout`].
+    pub holdouts: Vec<String>,
+    pub splits: Vec<SplitManifest>,
+}
+
+/// Generates every split to its own directory in `config.output_dir` and saves the manifest there.
+///
+/// Sizes of each split replace `config.sizes`, and `config.task_sizes` must be empty. They must not overlap, so no example can be both in train and test, unless one of the splits is held out and the other is not.
+pub fn generate_splits(config: &GenerateConfig, splits: &[Split]) -> Result<Manifest> {
+    if !config.task_sizes.is_empty() {
+        return Err(Error::Split("task sizes can't be combined with splits, each split has its own sizes".to_string()));
+    }
+    for (i, first) in splits.iter().enumerate() {
+        if first.held_out && config.holdouts.is_empty() {
+            return Err(Error::Split(format!("split `{}` is held out, but no attributes are held out", first.name)));
+        }
+        for second in &splits[i + 1..] {
+            if first.name == second.name {
+                return Err(Error::Split(format!("split `{}` is given twice", first.name)));
+            }
+            let overlap = first.sizes.min <= second.sizes.max && second.sizes.min <= first.sizes.max;
+            if overlap && first.held_out == second.held_out {
+                return Err(Error::Split(format!("splits `{}` and `{}` have overlapping sizes", first.name, second.name)));
+            }
+        }
+    }
+
+    let mut manifest = Manifest {
+        seed: config.seed,
+        examples: config.examples,
+        train_pairs: config.train_pairs,
+        colors: config.colors,
+        holdouts: config.holdouts.iter().map(Holdout::to_string).collect(),
+        splits: vec![],
+    };
+    for split in splits {
+        let split_config = GenerateConfig {
+            output_dir: config.output_dir.join(&split.name),
+            seed: split_seed(config.seed, &split.name),
+            sizes: split.sizes,
+            held_out: split.held_out,
+            ..config.clone()
+        };
+        // Messages about tasks are printed by `generate_all`, so they need to be told apart
+        eprintln!("generating split `{}`", split.name);
+        let tasks = generate_all(&split_config)?
+            .into_iter()
+            .map(|(task, stats)| TaskManifest { task, stats })
+            .collect();
+        manifest.splits.push(SplitManifest { name: split.name.clone(), sizes: split.sizes, held_out: split.held_out, seed: split_config.seed, tasks });
+    }
+    fs::create_dir_all(&config.output_dir)?;
+    save_json_to_file(&manifest, &config.output_dir.join("manifest.json"))?;
+    Ok(manifest)
+}
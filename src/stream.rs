@@ -0,0 +1,180 @@
+// Endless stream of examples from a weighted mixture of tasks
+
+use std::io::Write;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+use crate::dataset::{matches_filter, sizes_for, SizeRange};
+use crate::field::{check_colors, random_permutation, with_colors, COLORS};
+use crate::grid::Grid;
+use crate::registry::{Registry, TaskVariant};
+use crate::tasks::task_permute_colors;
+use crate::{Error, Result};
+
+/// One line of the stream. The example can be reproduced by calling `generate(size, rng)` of the task with `rng` seeded by `seed`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StreamExample {
+    pub task: String,
+    pub size: usize,
+    pub seed: u64,
+    pub input: Grid,
+    pub output: Grid,
+    /// Permutation of colors for tasks with [`crate::registry::Augmentation::Colors`], it's chosen by the same `rng` after the example is generated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permutation: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StreamConfig {
+    pub seed: u64,
+    /// Number of examples, `None` for endless stream.
+    pub count: Option<usize>,
+    /// Use only tasks whose name contains this string.
+    pub filter: Option<String>,
+    pub sizes: SizeRange,
+    /// Sizes for tasks whose name contains the string, they override `sizes`, last matching one wins.
+    pub task_sizes: Vec<(String, SizeRange)>,
+    /// Weights of tasks whose name contains the string, last matching one wins, other tasks have weight 1.
+    pub weights: Vec<(String, f64)>,
+    /// Number of colors, not counting the background. Tasks with fixed colors outside of it are not used.
+    pub colors: u8,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        StreamConfig {
+            seed: 42,
+            count: None,
+            filter: None,
+            sizes: SizeRange::default(),
+            task_sizes: vec![],
+            weights: vec![],
+            colors: COLORS,
+        }
+    }
+}
+
+/// Task is dropped from the stream after this many rejected examples in a row.
+pub const MAX_REJECTIONS: usize = 1000;
+
+/// Iterator over examples of randomly chosen tasks, examples are not deduplicated.
+///
+/// Tasks which reject [`MAX_REJECTIONS`] examples in a row are dropped with a message, the stream fails when no tasks are left.
+pub struct ExampleStream<'a> {
+    tasks: Vec<(&'a TaskVariant, SizeRange)>,
+    distribution: WeightedIndex<f64>,
+    /// Rejected examples of each task since its last generated one.
+    rejections: Vec<usize>,
+    rng: StdRng,
+    remaining: Option<usize>,
+    colors: u8,
+}
+
+impl<'a> ExampleStream<'a> {
+    pub fn new(registry: &'a Registry, config: &StreamConfig) -> Result<Self> {
+        check_colors(config.colors)?;
+        let mut tasks = vec![];
+        let mut weights = vec![];
+        for variant in registry.variants() {
+            if !matches_filter(&variant.name, config.filter.as_deref()) || variant.min_colors > config.colors {
+                continue;
+            }
+            let Some(sizes) = sizes_for(config.sizes, &config.task_sizes, variant) else {
+                continue;
+            };
+            let weight = config.weights.iter()
+                .rev()
+                .find(|(name, _)| variant.name.contains(name.as_str()))
+                .map_or(1.0, |(_, weight)| *weight);
+            if weight > 0.0 {
+                tasks.push((variant, sizes));
+                weights.push(weight);
+            }
+        }
+        let distribution = WeightedIndex::new(&weights)
+            .map_err(|err| Error::Stream(format!("can't sample tasks: {err}")))?;
+        Ok(ExampleStream {
+            rejections: vec![0; tasks.len()],
+            tasks,
+            distribution,
+            rng: StdRng::seed_from_u64(config.seed),
+            remaining: config.count,
+            colors: config.colors,
+        })
+    }
+}
+
+impl Iterator for ExampleStream<'_> {
+    type Item = Result<StreamExample>;
+
+    fn next(&mut self) -> Option<Result<StreamExample>> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        loop {
+            let task = self.distribution.sample(&mut self.rng);
+            let (variant, sizes) = self.tasks[task];
+            let size = sizes.sample(&mut self.rng);
+            let seed = self.rng.gen();
+            let generated = with_colors(self.colors, || {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let example = variant.generate(size, &mut rng)?;
+                if !variant.permutes_colors() {
+                    return Some((example, None));
+                }
+                let permutation = random_permutation(variant.fixed_colors, &mut rng);
+                Some((task_permute_colors(Some(example), &permutation)?, Some(permutation)))
+            });
+            let Some((example, permutation)) = generated else {
+                self.rejections[task] += 1;
+                if self.rejections[task] < MAX_REJECTIONS {
+                    continue;
+                }
+                eprintln!("dropping task `{}` from the stream: {MAX_REJECTIONS} examples in a row were rejected", variant.name);
+                if self.distribution.update_weights(&[(task, &0.0)]).is_err() {
+                    // All weights are zero now
+                    self.remaining = Some(0);
+                    return Some(Err(Error::Stream("every task rejects all examples".to_string())));
+                }
+                continue;
+            };
+            self.rejections[task] = 0;
+            if let Some(remaining) = &mut self.remaining {
+                *remaining -= 1;
+            }
+            return Some(Ok(StreamExample {
+                task: variant.name.clone(),
+                size,
+                seed,
+                input: example.input,
+                output: example.output,
+                permutation,
+            }));
+        }
+    }
+}
+
+/// Writes examples as json lines, stops without error when the reader is closed.
+///
+/// Every line is flushed, so a reader of the endless stream gets examples as soon as they are generated.
+pub fn write_stream(stream: ExampleStream, out: impl Write) -> Result<()> {
+    let mut out = std::io::BufWriter::new(out);
+    for example in stream {
+        let line = serde_json::to_string(&example?)?;
+        if let Err(err) = writeln!(out, "{line}").and_then(|()| out.flush()) {
+            return ignore_broken_pipe(err);
+        }
+    }
+    Ok(())
+}
+
+fn ignore_broken_pipe(err: std::io::Error) -> Result<()> {
+    if err.kind() == std::io::ErrorKind::BrokenPipe {
+        Ok(())
+    } else {
+        Err(err.into())
+    }
+}
@@ -0,0 +1,1712 @@
+// This code generates some 1D random riddles, with question and answer.
+
+// Generators keep the explicit returns of the code they were ported from
+#![allow(clippy::needless_return)]
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::prelude::SliceRandom;
+
+use crate::field::*;
+use crate::grid::Grid;
+use crate::layout::{max_blocks, place_blocks, render_blocks, sample_lengths, split_evenly};
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq, Default, Hash, Eq)]
+pub struct Example {
+    pub input: Grid,
+    pub output: Grid,
+}
+
+// ---------------------------------------------------------------------------
+
+// Actually riddles
+
+pub fn task_move_n_pix(size: usize, move_pix: usize, solid: bool, rng: &mut StdRng) -> Option<Example> {
+    /* some solid block is moved to the right for move_pix pixels */
+
+    if size <= move_pix+1 {
+        return None;
+    }
+
+    // We generate size and position in such a way, that there is no need to wrap
+    let block_size = rng.gen_range(1..size - move_pix);
+    let block_pos = rng.gen_range(0..=size - block_size - move_pix);
+    let block = if solid {
+        gen_field_color(block_size, random_color(rng))?
+    } else {
+        gen_random_field(block_size, rng)
+    };
+
+    let question = write_block(block_pos, &block, gen_field(size))?;
+    let answer = write_block(block_pos + move_pix, &block, gen_field(size))?;
+
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn task_move_n_pix_wrapped(size: usize, move_pix: usize, solid: bool, rng: &mut StdRng) -> Option<Example> {
+    /* some solid block is moved to the right for move_pix pixels, and if it exceed borders, it's wrapped to another side of the field */
+    if size < 2 {
+        return None;
+    }
+    let block_size = rng.gen_range(1..size);
+    let block_pos = rng.gen_range(0..size);
+    let block = if solid {
+        gen_field_color(block_size, random_color(rng))?
+    } else {
+        gen_random_field(block_size, rng)
+    };
+
+    let question = write_block_wrapped(block_pos, &block, gen_field(size))?;
+    let answer = write_block_wrapped(block_pos + move_pix, &block, gen_field(size))?;
+
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn task_gravity(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* everything is attracted to the left */
+    let question = gen_random_sparse_field(size, 0.5, rng);
+    let q = remove_color(0, question.clone());
+    let answer = write_block(size - q.len(), &q, gen_field(size))?;
+
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn task_gravity_counting(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* everything is attracted to the left, and afterwards color is changed to the 1 */
+    let question = gen_random_sparse_field(size, 0.5, rng);
+    let q_len = remove_color(0, question.clone()).len();
+    let block = gen_field_color(q_len, 1)?;
+    let answer = write_block(0, &block, gen_field(size))?;
+
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+
+pub fn task_gravity_antigravity(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* color 1 is moved to the right, color 2 is to the left */
+    let question = gen_random_sparse_field_two_colors(size, 0.5, rng)?;
+    let q1 = remove_color(2, remove_color(0, question.clone()));
+    let q2 = remove_color(1, remove_color(0, question.clone()));
+    let answer = write_block(0, &q1, write_block(size - q2.len(), &q2, gen_field(size))?)?;
+
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+
+// These tasks generated using LLMs
+
+pub fn task_block_touch_dot(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There is solid block of one color, and one dot of the other color on random place (not on block), block is moved until it touches that dot (not covers it). Color of a dot is not random and constant. */
+    if size < 2 {
+        return None;
+    }
+    let dot_color = 1u8;
+    let block_color = permute_color_not_black(dot_color, rng);
+
+    let block_size = rng.gen_range(1..size);
+    let dot_pos = rng.gen_range(0..size);
+
+    let can_place_left = dot_pos >= block_size;
+    let can_place_right = dot_pos + block_size < size;
+
+    if can_place_left || can_place_right {
+        let mut possible_sides = Vec::new();
+        if can_place_left {
+            possible_sides.push("left");
+        }
+        if can_place_right {
+            possible_sides.push("right");
+        }
+        let side = possible_sides[rng.gen_range(0..possible_sides.len())];
+        let (q_block_pos, a_block_pos) = match side {
+            "left" => {
+                let qpos = rng.gen_range(0..=dot_pos - block_size);
+                (qpos, dot_pos - block_size)
+            }
+            "right" => {
+                let qpos = rng.gen_range(dot_pos + 1..=size - block_size);
+                (qpos, dot_pos + 1)
+            }
+            _ => unreachable!(),
+        };
+
+        let block = gen_field_color(block_size, block_color)?;
+
+        let mut question = gen_field(size);
+        question = write_block(q_block_pos, &block, question)?;
+        question.set(dot_pos, dot_color).ok()?;
+
+        let mut answer = gen_field(size);
+        answer = write_block(a_block_pos, &block, answer)?;
+        answer.set(dot_pos, dot_color).ok()?;
+
+        return Some(Example {
+            input: question,
+            output: answer,
+        });
+    }
+
+    return None;
+}
+
+pub fn task_block_touch_dot_n_pix(size: usize, move_pix: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_block_touch_dot but block is moved only N pixel towards this goal. */
+    if size < 2 {
+        return None;
+    }
+    let dot_color = 2u8;
+    let block_color = permute_color_not_black(dot_color, rng);
+
+    let block_size = rng.gen_range(1..size);
+    let dot_pos = rng.gen_range(0..size);
+
+    let can_place_left = dot_pos >= block_size;
+    let can_place_right = dot_pos + block_size < size;
+
+    if can_place_left || can_place_right {
+        let mut possible_sides = Vec::new();
+        if can_place_left {
+            possible_sides.push("left");
+        }
+        if can_place_right {
+            possible_sides.push("right");
+        }
+        let side = possible_sides[rng.gen_range(0..possible_sides.len())];
+
+        let block = gen_field_color(block_size, block_color)?;
+        let (q_block_pos, a_block_pos) = match side {
+            "left" => {
+                let qpos = rng.gen_range(0..=dot_pos - block_size);
+                let distance = (dot_pos - block_size).saturating_sub(qpos);
+                let d = distance.min(move_pix);
+                (qpos, qpos + d)
+            }
+            "right" => {
+                let qpos = rng.gen_range(dot_pos + 1..=size - block_size);
+                let distance = qpos.saturating_sub(dot_pos + 1);
+                let d = distance.min(move_pix);
+                (qpos, qpos - d)
+            }
+            _ => unreachable!(),
+        };
+
+        let mut question = gen_field(size);
+        question = write_block(q_block_pos, &block, question)?;
+        question.set(dot_pos, dot_color).ok()?;
+
+        let mut answer = gen_field(size);
+        answer = write_block(a_block_pos, &block, answer)?;
+        answer.set(dot_pos, dot_color).ok()?;
+
+        return Some(Example {
+            input: question,
+            output: answer,
+        });
+    }
+
+    return None;
+}
+
+pub fn task_block_scale_to_dot(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_block_touch_dot, but block is scaled to this point (it's farthest end remains on its place, but other end it moved to touch the dot). */
+    if size < 2 {
+        return None;
+    }
+    let dot_color = 2u8;
+    let block_color = permute_color_not_black(dot_color, rng);
+
+    let block_size = rng.gen_range(1..size);
+    let dot_pos = rng.gen_range(0..size);
+
+    let can_place_left = dot_pos >= block_size;
+    let can_place_right = dot_pos + block_size < size;
+
+    if can_place_left || can_place_right {
+        let mut possible_sides = Vec::new();
+        if can_place_left {
+            possible_sides.push("left");
+        }
+        if can_place_right {
+            possible_sides.push("right");
+        }
+        let side = possible_sides[rng.gen_range(0..possible_sides.len())];
+
+        let q_block_pos = match side {
+            "left" => rng.gen_range(0..=dot_pos - block_size),
+            "right" => rng.gen_range(dot_pos + 1..=size - block_size),
+            _ => unreachable!(),
+        };
+
+        let block = gen_field_color(block_size, block_color)?;
+
+        let mut question = gen_field(size);
+        question = write_block(q_block_pos, &block, question)?;
+        question.set(dot_pos, dot_color).ok()?;
+
+        let (a_block_pos, scaled_size) = match side {
+            "left" => {
+                let new_size = dot_pos - q_block_pos + 1;
+                (q_block_pos, new_size)
+            }
+            "right" => {
+                let new_size = (q_block_pos + block_size) - dot_pos;
+                (dot_pos, new_size)
+            }
+            _ => unreachable!(),
+        };
+
+        let scaled_block = gen_field_color(scaled_size, block_color)?;
+
+        let mut answer = gen_field(size);
+        answer = write_block(a_block_pos, &scaled_block, answer)?;
+        answer.set(dot_pos, dot_color).ok()?;
+
+        return Some(Example {
+            input: question,
+            output: answer,
+        });
+    }
+
+    return None;
+}
+
+pub fn task_two_points_and_fill(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are only two points of the same color, and in answer between them everything is filled with this color. */
+    let c = random_color(rng);
+    let p1 = rng.gen_range(0..size);
+    let p2 = rng.gen_range(0..size);
+
+    if p1 == p2 {
+        return None;
+    }
+    let (pos1, pos2) = if p1 < p2 { (p1, p2) } else { (p2, p1) };
+
+    let mut question = gen_field(size);
+    question.set(pos1, c).ok()?;
+    question.set(pos2, c).ok()?;
+
+    let mut answer = question.clone();
+    for i in pos1..=pos2 {
+        answer.set(i, c).ok()?;
+    }
+
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_reflect_block_with_border_pixel(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* We have solid block with one pixel at left or right border of diferent color, we just reflect that block. */
+    if size < 2 {
+        return None;
+    }
+    let block_size = rng.gen_range(2..=size);
+    if block_size > size {
+        return None; 
+    }
+
+    let c1 = random_color(rng);
+    let c2 = random_color(rng);
+    if c1 == c2 {
+        return None; 
+    }
+
+    let side = if rng.gen_bool(0.5) { "left" } else { "right" };
+    let pos = rng.gen_range(0..=size - block_size);
+    let mut block = gen_field_color(block_size, c1)?;
+    match side {
+        "left" => block.set(0, c2).ok()?,
+        "right" => block.set(block_size - 1, c2).ok()?,
+        _ => unreachable!(),
+    }
+
+    let question = write_block(pos, &block, gen_field(size))?;
+    let reversed_block: Vec<u8> = block.iter().rev().copied().collect();
+    let answer = write_block(pos, &reversed_block, gen_field(size))?;
+
+    return Some(Example { input: question, output: answer });
+}
+
+pub fn task_reflect_block_with_border_pixel_random(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_reflect_block_with_border_pixel, but with block consists of random colors. */
+    if size < 2 {
+        return None;
+    }
+    let block_size = rng.gen_range(2..=size);
+    if block_size > size {
+        return None;
+    }
+
+    let side = if rng.gen_bool(0.5) { "left" } else { "right" };
+    let pos = rng.gen_range(0..=size - block_size);
+    let mut block = gen_random_field(block_size, rng);
+    let border_color = random_color(rng);
+    match side {
+        "left" => {
+            if block[0] == border_color {
+                return None;
+            }
+            block.set(0, border_color).ok()?;
+        }
+        "right" => {
+            if block[block_size - 1] == border_color {
+                return None;
+            }
+            block.set(block_size - 1, border_color).ok()?;
+        }
+        _ => unreachable!(),
+    }
+    let question = write_block(pos, &block, gen_field(size))?;
+    let reversed_block: Vec<u8> = block.iter().rev().copied().collect();
+    let answer = write_block(pos, &reversed_block, gen_field(size))?;
+
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_reflect_block_around_dot(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* We have some constant color dot, and block somewhere. And we just reflect that block around this dot. */
+    let dot_color = 2u8;
+
+    let dot_pos = rng.gen_range(0..size);
+    let block_size = rng.gen_range(1..=size);
+    let block_pos = rng.gen_range(0..=size - block_size);
+    let block_end = block_pos + block_size - 1;
+    let strictly_left  = block_end < dot_pos;
+    let strictly_right = block_pos > dot_pos;
+
+    if !(strictly_left || strictly_right) {
+        return None;
+    }
+    let block = gen_field_color(block_size, permute_color_not_black(dot_color, rng))?;
+    let min_reflect = 2 * dot_pos as isize - block_end as isize;
+    let max_reflect = 2 * dot_pos as isize - block_pos as isize;
+    if min_reflect < 0 || max_reflect >= size as isize {
+        return None;
+    }
+    let mut question = gen_field(size);
+    question = write_block(block_pos, &block, question)?;
+    question.set(dot_pos, dot_color).ok()?;
+    let mut answer = gen_field(size);
+    answer.set(dot_pos, dot_color).ok()?;
+    for i in 0..block_size {
+        let reflect_idx = (2 * dot_pos) as isize - (block_pos + i) as isize;
+        answer.set(reflect_idx as usize, block[i]).ok()?;
+    }
+
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+#[allow(clippy::needless_range_loop)]
+pub fn task_block_and_noise_remove(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* We have some block and some noise of the same color, and we remove that noise (make sure that noise do not create block with size 2, or that noise does not touch block, so that's impossible to restore original picture). */
+    if size < 2 {
+        return None;
+    }
+    let c = random_color(rng);
+    let block_size = rng.gen_range(2..=size);
+    if block_size > size {
+        return None;
+    }
+    let block_pos = rng.gen_range(0..=size - block_size);
+    let mut field = gen_field(size);
+    for i in 0..block_size {
+        field.set(block_pos + i, c).ok()?;
+    }
+    let mut forbidden = vec![false; size];
+    for i in block_pos..(block_pos + block_size) {
+        forbidden[i] = true;
+    }
+    if block_pos > 0 {
+        forbidden[block_pos - 1] = true;
+    }
+    if block_pos + block_size < size {
+        forbidden[block_pos + block_size] = true;
+    }
+    let noise_count = rng.gen_range(1..=3);
+    let mut noise_positions = vec![];
+
+    for _ in 0..noise_count {
+        let allowed_positions: Vec<usize> =
+            (0..size).filter(|&i| !forbidden[i]).collect();
+
+        if allowed_positions.is_empty() {
+            break;
+        }
+        let noise_pos = allowed_positions[rng.gen_range(0..allowed_positions.len())];
+        noise_positions.push(noise_pos);
+        field.set(noise_pos, c).ok()?;
+        forbidden[noise_pos] = true;
+        if noise_pos > 0 {
+            forbidden[noise_pos - 1] = true;
+        }
+        if noise_pos + 1 < size {
+            forbidden[noise_pos + 1] = true;
+        }
+    }
+    if noise_positions.len() < noise_count {
+        return None;
+    }
+    let question = field.clone();
+    let mut answer = field.clone();
+    for &p in &noise_positions {
+        answer.set(p, 0).ok()?;
+    }
+
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_block_and_noise_remove_inside(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_block_and_noise_remove, but noise is inside the block, and it has different colors. */
+    if size <= 6 {
+        return None;
+    }
+    let c = random_color(rng);
+    let block_size = rng.gen_range(6..=size);
+    if block_size > size {
+        return None;
+    }
+    let block_pos = rng.gen_range(0..=size - block_size);
+    let mut field = gen_field(size);
+    for i in 0..block_size {
+        field.set(block_pos + i, c).ok()?;
+    }
+    let max_noise = (block_size / 2-1).max(1);
+    let noise_count = rng.gen_range(1..=max_noise);
+    let mut indices: Vec<usize> = (0..block_size).collect();
+    indices.shuffle(rng);
+    let noise_positions: Vec<usize> = indices.into_iter().take(noise_count).collect();
+    for &offset in &noise_positions {
+        let pos = block_pos + offset;
+        let noise_color = permute_color_not_black(c, rng);
+        field.set(pos, noise_color).ok()?;
+    }
+    let question = field.clone();
+    let mut answer = field.clone();
+    for &offset in &noise_positions {
+        let pos = block_pos + offset;
+        answer.set(pos, c).ok()?;
+    }
+
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_copy_block_to_dots(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are block of some odd size (3 or 5) at the start and then some dots. We just copy this block to center of the each dot (dots should be on a distance that resulting blocks does not touch and does not overlap). Color of block and dots is the same, but overall random. */
+    let block_size = if rng.gen_bool(0.5) { 3 } else { 5 };
+    if block_size >= size { return None; }
+    
+    let color = random_color(rng);
+    let block = gen_field_color(block_size, color)?;
+    
+    // Generate dots with minimum distance to prevent overlap
+    let min_gap = block_size;
+    let mut dot_positions = Vec::new();
+    let mut pos = block_size + block_size/2 + 1;
+    
+    while pos <= size - block_size {
+        if rng.gen_bool(0.5) { // Control dot density
+            dot_positions.push(pos);
+            pos += min_gap;
+        }
+        pos += 1;
+    }
+    
+    if dot_positions.is_empty() { return None; }
+    
+    let mut question = gen_field(size);
+    question = write_block(0, &block, question)?;
+    for &pos in &dot_positions {
+        question.set(pos, color).ok()?;
+    }
+    
+    let mut answer = gen_field(size);
+    answer = write_block(0, &block, answer)?;
+    for &pos in &dot_positions {
+        let block_start = pos - block_size/2;
+        answer = write_block(block_start, &block, answer)?;
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_copy_block_to_dots_colors(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_copy_block_to_dots, but color of everything is different, and when we place block, we just copy that color. */
+    let block_size = if rng.gen_bool(0.5) { 3 } else { 5 };
+    if block_size >= size { return None; }
+    
+    let block_color = random_color(rng);
+    let block = gen_field_color(block_size, block_color)?;
+    
+    // Generate dots with minimum distance to prevent overlap
+    let min_gap = block_size;
+    let mut dot_positions = Vec::new();
+    let mut dot_colors = Vec::new();
+    let mut pos = block_size + block_size/2 + 1;
+    
+    while pos < size - block_size {
+        if rng.gen_bool(0.5) {
+            let dot_color = random_color(rng);
+            dot_positions.push(pos);
+            dot_colors.push(dot_color);
+            pos += min_gap;
+        }
+        pos += 1;
+    }
+    
+    if dot_positions.is_empty() { return None; }
+    
+    let mut question = gen_field(size);
+    question = write_block(0, &block, question)?;
+    for (i, &pos) in dot_positions.iter().enumerate() {
+        question.set(pos, dot_colors[i]).ok()?;
+    }
+    
+    let mut answer = gen_field(size);
+    answer = write_block(0, &block, answer)?;
+    for (i, &pos) in dot_positions.iter().enumerate() {
+        let block_start = pos - block_size/2;
+        let colored_block = gen_field_color(block_size, dot_colors[i])?;
+        answer = write_block(block_start, &colored_block, answer)?;
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_paint_biggest_block(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* We have some amount of blocks of some constant color, and we just paint biggest of them to another constant color. */
+    let target_color = 1u8;
+    let initial_color = permute_color_not_black(target_color, rng);
+    if initial_color == 0 {
+        return None;
+    }
+    
+    // Generate random blocks
+    let mut question = gen_field(size);
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    
+    while pos < size {
+        if rng.gen_bool(0.4) && size - pos >= 2 {
+            let block_size = rng.gen_range(2..=((size - pos).min(6)));
+            blocks.push((pos, block_size));
+            for i in 0..block_size {
+                question.set(pos + i, initial_color).ok()?;
+            }
+            pos += block_size + 1;
+        } else {
+            pos += 1;
+        }
+    }
+    
+    if blocks.len() < 2 { return None; }  // Need at least two blocks
+    
+    // Find biggest block
+    let (biggest_pos, biggest_size) = *blocks.iter()
+        .max_by_key(|(_pos, size)| *size)
+        .unwrap();
+
+    let biggest_count = blocks.iter()
+        .filter(|(_pos, size)| *size == biggest_size)
+        .count();
+
+    if biggest_count > 1 {
+        return None;
+    }
+        
+    // Create answer by recoloring the biggest block
+    let mut answer = question.clone();
+    for i in 0..biggest_size {
+        answer.set(biggest_pos + i, target_color).ok()?;
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_sort_blocks_by_size(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are many blocks of the same color and different length. We sort them by size. After sorting smallest block is on left side, and distance between blocks equal to 1. */
+    let color = random_color(rng);
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    
+    // Generate random blocks with random sizes
+    while pos < size {
+        if rng.gen_bool(0.4) && size - pos >= 2 {
+            let block_size = rng.gen_range(1..=((size - pos).min(6)));
+            blocks.push((pos, block_size));
+            pos += block_size + rng.gen_range(1..=4); // Random gaps between blocks
+        } else {
+            pos += 1;
+        }
+    }
+    
+    if blocks.len() < 2 { return None; } // Need at least two blocks
+    
+    // Create input field
+    let mut question = gen_field(size);
+    for &(pos, size) in &blocks {
+        for i in 0..size {
+            question.set(pos + i, color).ok()?;
+        }
+    }
+    
+    // Sort blocks by size
+    blocks.sort_by_key(|&(_, size)| size);
+    
+    // Check if sorted blocks fit with gaps
+    let total_space = blocks.iter().map(|&(_, size)| size).sum::<usize>() + blocks.len() - 1;
+    if total_space > size { return None; }
+    
+    // Create answer field with sorted blocks
+    let mut answer = gen_field(size);
+    let mut current_pos = 0;
+    
+    for &(_, block_size) in &blocks {
+        for i in 0..block_size {
+            answer.set(current_pos + i, color).ok()?;
+        }
+        current_pos += block_size + 1; // One pixel gap
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_sort_complete_sequence(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_sort_blocks_by_size, but there are all block sizes from 1 to maximum possible number (how much size allows), with gap size 1. And there are random permutation of this on the input. */
+    // Calculate max possible block size given total array size
+    let mut max_size = 1;
+    let mut total_space = 0;
+    while total_space + max_size < size {
+        total_space += max_size + 1;
+        max_size += 1;
+    }
+    if max_size < 2 { return None; }
+    max_size -= 1;
+    
+    let color = random_color(rng);
+    
+    // Create sequence of all sizes from 1 to max_size
+    let mut blocks: Vec<usize> = (1..=max_size).collect();
+    blocks.shuffle(rng);
+    
+    // Create input field with shuffled blocks
+    let mut question = gen_field(size);
+    let mut pos = 0;
+    for &block_size in &blocks {
+        for i in 0..block_size {
+            question.set(pos + i, color).ok()?;
+        }
+        pos += block_size + 1;
+    }
+    
+    // Create answer field with sorted blocks
+    let mut answer = gen_field(size);
+    let mut pos = 0;
+    for block_size in 1..=max_size {
+        for i in 0..block_size {
+            answer.set(pos + i, color).ok()?;
+        }
+        pos += block_size + 1;
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_recolor_blocks_by_size(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are two blocks of random size (not equal size) of color 3. Biggest block painted as color 1, smallest block painted as color 2. */
+    // Generate two different random sizes
+    let size1 = rng.gen_range(2..=8);
+    let mut size2 = rng.gen_range(2..=8);
+    while size2 == size1 {
+        size2 = rng.gen_range(2..=8);
+    }
+    
+    // Ensure both blocks fit with at least 1 gap
+    if size1 + size2 + 1 > size { return None; }
+    
+    // Place blocks with gap
+    let pos1 = rng.gen_range(0..=size - (size1 + size2 + 1));
+    let pos2 = rng.gen_range(pos1 + size1 + 1..=size - size2);
+    
+    // Create input field with both blocks color 3
+    let mut question = gen_field(size);
+    for i in 0..size1 {
+        question.set(pos1 + i, 3).ok()?;
+    }
+    for i in 0..size2 {
+        question.set(pos2 + i, 3).ok()?;
+    }
+    
+    // Create answer field with recolored blocks
+    let mut answer = question.clone();
+    if size1 > size2 {
+        for i in 0..size1 { answer.set(pos1 + i, 1).ok()?; }
+        for i in 0..size2 { answer.set(pos2 + i, 2).ok()?; }
+    } else {
+        for i in 0..size1 { answer.set(pos1 + i, 2).ok()?; }
+        for i in 0..size2 { answer.set(pos2 + i, 1).ok()?; }
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_gravity_one_step(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Gravity to the left, but any point can be moved only 1 pixel towards left. */
+    let question = gen_random_sparse_field(size, 0.5, rng);
+    let mut answer = question.clone();
+    
+    // Move each non-zero pixel one step left if possible
+    for i in 1..size {
+        if answer[i] != 0 && answer[i-1] == 0 {
+            answer.set(i-1, answer[i]).ok()?;
+            answer.set(i, 0).ok()?;
+        }
+    }
+    
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn task_move_block_by_own_size(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There is only one solid block. It's moved to the right for size of that block pixels. */
+    if size < 2 {
+        return None;
+    }
+    let block_size = rng.gen_range(1..=size/2);  // Ensure space for movement
+    let pos = rng.gen_range(0..=size - block_size * 2);  // Space for block and movement
+    let color = random_color(rng);
+    
+    let mut question = gen_field(size);
+    let block = gen_field_color(block_size, color)?;
+    question = write_block(pos, &block, question)?;
+    
+    let answer = write_block(pos + block_size, &block, gen_field(size))?;
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_change_to_five(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Every non-zero color is changed to color 5. */
+    let question = gen_random_sparse_field(size, 0.5, rng);
+    let answer = Grid::from_cells(question.iter()
+        .map(|&x| if x != 0 { 5 } else { 0 })
+        .collect()).ok()?;
+    
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn task_recolor_blocks_from_palette(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are many blocks with same size (range: 2..=4) and color 5. And then there is random colors (except 0) at the most left corner. There are as many these colors as there blocks. In output all these blocks are recolored according to this colors. */
+    // Generate blocks of same size
+    let block_size = rng.gen_range(2..=4);
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    
+    while pos + block_size <= size {
+        if rng.gen_bool(0.4) {
+            blocks.push(pos);
+            pos += block_size + 1;
+        } else {
+            pos += 1;
+        }
+    }
+    // Each block needs its own color
+    blocks.truncate(colors() as usize);
+
+    loop {
+        let palette_size = blocks.len();
+        if blocks.last()? + block_size + palette_size + 1 >= size {
+            blocks.pop();
+        } else {
+            break;
+        }
+    }
+
+    let palette_size = blocks.len();
+    for block in &mut blocks {
+        *block += palette_size + 1;
+    }
+    
+    if blocks.is_empty() { return None; }
+    
+    // Generate color palette
+    let mut colors: Vec<u8> = Vec::new();
+    for _ in 0..blocks.len() {
+        let mut color;
+        loop {
+            color = random_color(rng);
+            if !colors.contains(&color) { break; }
+        }
+        colors.push(color);
+    }
+    
+    // Create question with color palette and blocks
+    let mut question = gen_field(size);
+    
+    // Place color palette at start
+    for (i, &color) in colors.iter().enumerate() {
+        question.set(i, color).ok()?;
+    }
+    
+    // Place blocks of color 5
+    for &block_pos in &blocks {
+        for i in 0..block_size {
+            question.set(block_pos + i, 5).ok()?;
+        }
+    }
+    
+    // Create answer with recolored blocks
+    let mut answer = question.clone();
+    for (block_idx, &block_pos) in blocks.iter().enumerate() {
+        let color = colors[block_idx];
+        for i in 0..block_size {
+            answer.set(block_pos + i, color).ok()?;
+        }
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_duplicate_block_from_seeds(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are one block with size at least 2 with color 1. And then there one pixel on right or on left or on both sides with random color, with distance 1 to that block. And block is being duplicated, starting from each pixel with this pixel color, and repeated indefinitely until it reaches end of the field. Block can be drawn partially on border (draw block while current position is inside field). */
+    let block_size = rng.gen_range(2..=4);
+    if block_size + 1 >= size { return None; }
+    if size <= 3 + block_size { return None; }
+    
+    // Position block with space for seeds
+    let block_pos = rng.gen_range(2..size - block_size - 1);
+    
+    // Decide seed placement
+    let left_seed = rng.gen_bool(0.5);
+    let right_seed = rng.gen_bool(0.5);
+    if !left_seed && !right_seed { return None; }
+    
+    // Create input
+    let mut question = gen_field(size);
+    
+    // Place main block
+    for i in 0..block_size {
+        question.set(block_pos + i, 1).ok()?;
+    }
+    
+    // Place seeds with gaps
+    let mut seeds = Vec::new();
+    if left_seed {
+        let color = random_color(rng);
+        question.set(block_pos - 2, color).ok()?;
+        seeds.push(("left", block_pos - 2, color));
+    }
+    if right_seed {
+        let color = random_color(rng);
+        question.set(block_pos + block_size + 1, color).ok()?;
+        seeds.push(("right", block_pos + block_size + 1, color));
+    }
+    
+    // Create answer with duplicated blocks
+    let mut answer = question.clone();
+    
+    for &(side, seed_pos, color) in &seeds {
+        match side {
+            // For left seed, blocks end at seed
+            "left" => {
+                let mut end_pos = seed_pos as i32;
+                while end_pos >= 0 {
+                    let start_pos = end_pos - block_size as i32 + 1;
+                    for pos in start_pos..=end_pos {
+                        if pos >= 0 && (pos as usize) < size {
+                            answer.set(pos as usize, color).ok()?;
+                        }
+                    }
+                    if start_pos < 1 { break; }
+                    end_pos = start_pos - 2; // -1 for gap
+                }
+            },
+            // For right seed, blocks start at seed
+            "right" => {
+                let mut start_pos = seed_pos;
+                while start_pos < size {
+                    for offset in 0..block_size {
+                        if start_pos + offset >= size { break; }
+                        answer.set(start_pos + offset, color).ok()?;
+                    }
+                    if start_pos + block_size + 1 >= size { break; }
+                    start_pos = start_pos + block_size + 1; // +1 for gap
+                }
+            },
+            _ => unreachable!(),
+        }
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_fill_from_pixel(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There is some solid block of random color with size at least 3, and one pixel with random color on left or right side. This pixel fills right or left side with its color. */
+    let block_size = rng.gen_range(3..=6);
+    if block_size + 2 >= size { return None; }
+    
+    // Position block with space for seed
+    let block_pos = rng.gen_range(1..size - block_size - 1);
+    
+    // Create input
+    let mut question = gen_field(size);
+    
+    // Place main block
+    let block_color = random_color(rng);
+    for i in 0..block_size {
+        question.set(block_pos + i, block_color).ok()?;
+    }
+    
+    // Place seed pixel and determine fill direction
+    let seed_color = permute_color_not_black(block_color, rng);
+    let is_left = rng.gen_bool(0.5);
+    
+    if is_left {
+        question.set(block_pos - 1, seed_color).ok()?;
+    } else {
+        question.set(block_pos + block_size, seed_color).ok()?;
+    }
+    
+    // Create answer with fill
+    let mut answer = question.clone();
+    
+    if is_left {
+        // Fill from seed to left border
+        for i in 0..block_pos {
+            answer.set(i, seed_color).ok()?;
+        }
+    } else {
+        // Fill from seed to right border
+        for i in (block_pos + block_size)..size {
+            answer.set(i, seed_color).ok()?;
+        }
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_mark_size_two_blocks(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are many blocks of size from 1 to 3 with gap at least 2 with color 1. Each block with size 2 is surrounded with pixels of color 3 at its sides. */
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    
+    // Generate blocks with minimum gap of 2
+    while pos < size {
+        if rng.gen_bool(0.4) {
+            let block_size = rng.gen_range(1..=3);
+            // Check if we have space for block and potential markers
+            let needed_space = block_size + if block_size == 2 { 2 } else { 0 };
+            if pos + needed_space < size {
+                blocks.push((pos, block_size));
+                pos += block_size + 2; // Minimum gap of 2
+            }
+        }
+        pos += 1;
+    }
+    
+    if blocks.len() < 2 { return None; }
+    
+    // Verify gaps between blocks (including markers)
+    let mut valid = true;
+    for i in 0..blocks.len()-1 {
+        let (pos1, size1) = blocks[i];
+        let (pos2, _) = blocks[i+1];
+        let needed_gap = if size1 == 2 { 3 } else { 2 };
+        if pos2 - (pos1 + size1) < needed_gap {
+            valid = false;
+            break;
+        }
+    }
+    if !valid { return None; }
+    
+    // Create input with blocks
+    let mut question = gen_field(size);
+    for &(pos, block_size) in &blocks {
+        // Place block
+        for i in 0..block_size {
+            question.set(pos + i, 1).ok()?;
+        }
+    }
+    
+    // Create answer with markers
+    let mut answer = question.clone();
+    for &(pos, block_size) in &blocks {
+        if block_size == 2 {
+            // Add markers for size 2 blocks
+            if pos > 0 {
+                answer.set(pos - 1, 3).ok()?;
+            }
+            if pos + block_size < size {
+                answer.set(pos + block_size, 3).ok()?;
+            }
+        }
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_color_left_half_blocks(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are many blocks with size from 2 to 8 with gap 1 and color 2. On the output left half of them is colored to color 8. */
+    let mut pos = 0;
+    let mut question = gen_field(size);
+    let mut blocks = Vec::new();
+    
+    // Generate blocks with gap 1
+    while pos < size {
+        if rng.gen_bool(0.4) {
+            let block_size = rng.gen_range(2..=8);
+            if pos + block_size >= size { break; }
+            
+            blocks.push((pos, block_size));
+            for i in 0..block_size {
+                question.set(pos + i, 2).ok()?;
+            }
+            pos += block_size + 1; // block size + gap
+        } else {
+            pos += 1;
+        }
+    }
+    
+    if blocks.len() < 2 { return None; }
+    
+    // Create answer with half-colored blocks
+    let mut answer = question.clone();
+    for &(pos, block_size) in &blocks {
+        let half_size = block_size / 2;
+        for i in 0..half_size {
+            answer.set(pos + i, 8).ok()?;
+        }
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_fill_until_collision(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are one pixel at left or right side with color 5, and there are couple of pixels with random color. Each pixel fills empty space with its color in the direction of pixel on the side, until it reaches another pixel. */
+    // At least 4 positions for meaningful puzzle
+    if size < 4 { return None; }
+    
+    let is_left = rng.gen_bool(0.5);
+    let mut question = gen_field(size);
+    
+    // Place the side marker
+    if is_left {
+        question.set(0, 5).ok()?;
+    } else {
+        question.set(size - 1, 5).ok()?;
+    }
+    
+    // Place 2-4 random pixels
+    let num_pixels = rng.gen_range(2..=4);
+    // Pixels need distinct positions besides the marker, otherwise they are never placed
+    if num_pixels >= size as i32 {
+        return None;
+    }
+    let mut positions = Vec::new();
+    
+    if is_left {
+        // Skip first position
+        for _ in 0..num_pixels {
+            let mut pos;
+            loop {
+                pos = rng.gen_range(1..size);
+                if !positions.contains(&pos) { break; }
+            }
+            positions.push(pos);
+        }
+    } else {
+        // Skip last position
+        for _ in 0..num_pixels {
+            let mut pos;
+            loop {
+                pos = rng.gen_range(0..size-1);
+                if !positions.contains(&pos) { break; }
+            }
+            positions.push(pos);
+        }
+    }
+    
+    // Color random pixels
+    for &pos in &positions {
+        question.set(pos, permute_color_not_black(5, rng)).ok()?;
+    }
+    
+    positions.sort_unstable();
+    
+    // Create answer
+    let mut answer = question.clone();
+    
+    if is_left {
+        // Fill right from each pixel
+        let mut prev_pos = 0; // Start from marker
+        for &pos in &positions {
+            let color = question[pos];
+            // Fill from previous position to current
+            for i in (prev_pos + 1)..pos {
+                answer.set(i, color).ok()?;
+            }
+            prev_pos = pos;
+        }
+    } else {
+        // Fill right from each pixel
+        let mut prev_pos = size-1; // Start from marker
+        for &pos in positions.iter().rev() {
+            let color = question[pos];
+            // Fill from previous position to current
+            for i in pos+1..prev_pos {
+                answer.set(i, color).ok()?;
+            }
+            prev_pos = pos;
+        }
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+#[allow(clippy::needless_range_loop)]
+pub fn task_repeat_pattern_full(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* At left corner there are set of random pixels with size in range 2..=5. And it's repeated two times. In output this pattern should be repeated as many times as possible. After repetition, there should be no 0 pixels. */
+    // Generate initial pattern
+    let pattern_size = rng.gen_range(2..=5);
+    let mut pattern = Vec::with_capacity(pattern_size);
+    for _ in 0..pattern_size {
+        pattern.push(random_color(rng));
+    }
+    
+    // Calculate total size needed for 2 repetitions
+    let double_size = pattern_size * 2;
+    if double_size >= size { return None; }
+    
+    // Create input with 2 repetitions
+    let mut question = gen_field(size);
+    for i in 0..pattern_size {
+        question.set(i, pattern[i]).ok()?;
+        question.set(i + pattern_size, pattern[i]).ok()?;
+    }
+    
+    // Create answer with maximum repetitions
+    let mut answer = gen_field(size);
+    let mut pos = 0;
+    while pos + pattern_size <= size {
+        for i in 0..pattern_size {
+            answer.set(pos + i, pattern[i]).ok()?;
+        }
+        pos += pattern_size;
+    }
+    
+    // Fill remaining space (if any) with pattern elements
+    for i in pos..size {
+        answer.set(i, pattern[i - pos]).ok()?;
+    }
+    
+    return Some(Example {
+        input: question,
+        output: answer,
+    });
+}
+
+pub fn task_gravity_weighted_colors(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Gravity to the left from the random field, but color 2 is heavier than color 1, so it's always all pixels of color 2, then pixels of color 1 at the output. */
+    // Generate random field with only colors 1 and 2
+    let question = Grid::from_cells((0..size).map(|_| {
+        if rng.gen_bool(0.5) {
+            if rng.gen_bool(0.5) { 1 } else { 2 }
+        } else {
+            0
+        }
+    }).collect()).ok()?;
+    
+    // Count colors
+    let count_1 = question.iter().filter(|&&x| x == 1).count();
+    let count_2 = question.iter().filter(|&&x| x == 2).count();
+    
+    // Create answer with sorted colors
+    let mut answer = gen_field(size);
+    
+    // Place heavier color 2 first
+    for i in 0..count_2 {
+        answer.set(i, 2).ok()?;
+    }
+    
+    // Then place color 1
+    for i in 0..count_1 {
+        answer.set(count_2 + i, 1).ok()?;
+    }
+    
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn task_crop_block(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There is one block of random colors somewhere in the field. Output is just this block without empty space around it. */
+    let block_size = rng.gen_range(1..=size);
+    let block_pos = rng.gen_range(0..=size - block_size);
+    let block = gen_random_field(block_size, rng);
+
+    let mut question = gen_field(size);
+    question = write_block(block_pos, &block, question)?;
+
+    Some(Example {
+        input: question,
+        output: block,
+    })
+}
+
+pub fn task_count_pixels(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* There are pixels of one random color scattered over the field. Output is a solid block of this color, its size is the number of pixels. */
+    let color = random_color(rng);
+    let question = Grid::from_cells((0..size).map(|_| if rng.gen_bool(0.3) { color } else { 0 }).collect()).ok()?;
+    let count = question.iter().filter(|&&x| x != 0).count();
+    if count == 0 { return None; }
+
+    Some(Example {
+        input: question,
+        output: gen_field_color(count, color)?,
+    })
+}
+
+pub fn task_compress_runs(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Field is fully covered by solid blocks of random size, neighbour blocks have different colors. Output has one pixel for each block. */
+    let mut question = Vec::new();
+    let mut answer = Vec::new();
+    let mut color = random_color(rng);
+    while question.len() < size {
+        let block_size = rng.gen_range(1..=4).min(size - question.len());
+        question.extend(std::iter::repeat_n(color, block_size));
+        answer.push(color);
+        color = permute_color_not_black(color, rng);
+    }
+
+    Some(Example {
+        input: Grid::from_cells(question).ok()?,
+        output: Grid::from_cells(answer).ok()?,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+
+// Constructive versions of tasks which reject a lot. They sample the layout first (number of blocks, their sizes and gaps) so that it fits the size, and then render it, so they never return None for sizes from `min_size` of the task.
+
+fn mirror_randomly(example: Example, rng: &mut StdRng) -> Option<Example> {
+    if rng.gen_bool(0.5) {
+        task_mirror(Some(example))
+    } else {
+        Some(example)
+    }
+}
+
+/// Solid block somewhere before the dot, returns `(block_pos, block_size, dot_pos)`.
+fn block_before_dot(size: usize, rng: &mut StdRng) -> Option<(usize, usize, usize)> {
+    if size < 2 {
+        return None;
+    }
+    let block_size = rng.gen_range(1..size);
+    let free = split_evenly(size - 1 - block_size, 3, rng);
+    Some((free[0], block_size, free[0] + block_size + free[1]))
+}
+
+fn block_and_dot(size: usize, block: (usize, usize), block_color: u8, dot_pos: usize, dot_color: u8) -> Option<Grid> {
+    let mut field = render_blocks(size, &[block], block_color)?;
+    field.set(dot_pos, dot_color).ok()?;
+    Some(field)
+}
+
+pub fn construct_block_touch_dot(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_block_touch_dot. */
+    let dot_color = 1u8;
+    let block_color = permute_color_not_black(dot_color, rng);
+    let (block_pos, block_size, dot_pos) = block_before_dot(size, rng)?;
+    mirror_randomly(Example {
+        input: block_and_dot(size, (block_pos, block_size), block_color, dot_pos, dot_color)?,
+        output: block_and_dot(size, (dot_pos - block_size, block_size), block_color, dot_pos, dot_color)?,
+    }, rng)
+}
+
+pub fn construct_block_touch_dot_n_pix(size: usize, move_pix: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_block_touch_dot_n_pix. */
+    let dot_color = 2u8;
+    let block_color = permute_color_not_black(dot_color, rng);
+    let (block_pos, block_size, dot_pos) = block_before_dot(size, rng)?;
+    let distance = dot_pos - block_pos - block_size;
+    mirror_randomly(Example {
+        input: block_and_dot(size, (block_pos, block_size), block_color, dot_pos, dot_color)?,
+        output: block_and_dot(size, (block_pos + distance.min(move_pix), block_size), block_color, dot_pos, dot_color)?,
+    }, rng)
+}
+
+pub fn construct_block_scale_to_dot(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_block_scale_to_dot. */
+    let dot_color = 2u8;
+    let block_color = permute_color_not_black(dot_color, rng);
+    let (block_pos, block_size, dot_pos) = block_before_dot(size, rng)?;
+    mirror_randomly(Example {
+        input: block_and_dot(size, (block_pos, block_size), block_color, dot_pos, dot_color)?,
+        output: block_and_dot(size, (block_pos, dot_pos - block_pos), block_color, dot_pos, dot_color)?,
+    }, rng)
+}
+
+pub fn construct_reflect_block_around_dot(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_reflect_block_around_dot. The block, the gap and their reflection are sampled first, what's left goes to the borders. */
+    if size < 3 {
+        return None;
+    }
+    let dot_color = 2u8;
+    let block_color = permute_color_not_black(dot_color, rng);
+    let block_size = rng.gen_range(1..=(size - 1) / 2);
+    let free = size - 1 - 2 * block_size;
+    let gap = rng.gen_range(0..=free / 2);
+    let borders = split_evenly(free - 2 * gap, 2, rng);
+    let dot_pos = borders[0] + block_size + gap;
+    mirror_randomly(Example {
+        input: block_and_dot(size, (borders[0], block_size), block_color, dot_pos, dot_color)?,
+        output: block_and_dot(size, (dot_pos + 1 + gap, block_size), block_color, dot_pos, dot_color)?,
+    }, rng)
+}
+
+pub fn construct_block_and_noise_remove(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_block_and_noise_remove. Noise pixels and the block are placed in random order with gaps at least 1. */
+    if size < 4 {
+        return None;
+    }
+    let c = random_color(rng);
+    let noise_count = rng.gen_range(1..=((size - 2) / 2).min(3));
+    let block_size = rng.gen_range(2..=size - 2 * noise_count);
+    let mut lengths = vec![1; noise_count];
+    let block_index = rng.gen_range(0..=noise_count);
+    lengths.insert(block_index, block_size);
+    let blocks = place_blocks(size, &lengths, &vec![1; noise_count], rng)?;
+
+    Some(Example {
+        input: render_blocks(size, &blocks, c)?,
+        output: render_blocks(size, &blocks[block_index..=block_index], c)?,
+    })
+}
+
+pub fn construct_paint_biggest_block(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_paint_biggest_block. The biggest block is sampled first, so it's always unique. */
+    // The biggest block has at least 3 pixels, others have at least 2 pixels and a gap
+    let max_count = size / 3;
+    if max_count < 2 {
+        return None;
+    }
+    let target_color = 1u8;
+    let initial_color = permute_color_not_black(target_color, rng);
+    let count = rng.gen_range(2..=max_count);
+    let biggest = rng.gen_range(3..=(size - 3 * (count - 1)).min(6));
+    let mut lengths = sample_lengths(count - 1, 2..=biggest - 1, size - biggest - (count - 1), rng)?;
+    let biggest_index = rng.gen_range(0..count);
+    lengths.insert(biggest_index, biggest);
+    let blocks = place_blocks(size, &lengths, &vec![1; count - 1], rng)?;
+
+    let question = render_blocks(size, &blocks, initial_color)?;
+    let mut answer = question.clone();
+    let (biggest_pos, _) = blocks[biggest_index];
+    answer.fill(biggest_pos..biggest_pos + biggest, target_color).ok()?;
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn construct_sort_blocks_by_size(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_sort_blocks_by_size. A block never starts at the last cell, so the last block of size 1 leaves it empty. */
+    let max_count = max_blocks(size.saturating_sub(1), 1, 1);
+    if max_count < 2 {
+        return None;
+    }
+    let color = random_color(rng);
+    let count = rng.gen_range(2..=max_count);
+    let budget = size - (count - 1);
+    let last = rng.gen_range(1..=(budget - (count - 1)).min(6));
+    let trailing = usize::from(last == 1);
+    let mut lengths = sample_lengths(count - 1, 1..=6, budget - last - trailing, rng)?;
+    lengths.push(last);
+    let blocks = place_blocks(size - trailing, &lengths, &vec![1; count - 1], rng)?;
+    let question = render_blocks(size, &blocks, color)?;
+
+    // Sorted blocks start at the left border with gaps of 1
+    lengths.sort();
+    let mut sorted = vec![];
+    let mut pos = 0;
+    for len in lengths {
+        sorted.push((pos, len));
+        pos += len + 1;
+    }
+    Some(Example {
+        input: question,
+        output: render_blocks(size, &sorted, color)?,
+    })
+}
+
+pub fn construct_recolor_blocks_by_size(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_recolor_blocks_by_size. The bigger block is sampled first, and the smaller one gets what's left. */
+    if size < 6 {
+        return None;
+    }
+    let bigger = rng.gen_range(3..=(size - 3).min(8));
+    let smaller = rng.gen_range(2..=(bigger - 1).min(size - 1 - bigger));
+    let mut lengths = [bigger, smaller];
+    lengths.shuffle(rng);
+    let blocks = place_blocks(size, &lengths, &[1], rng)?;
+
+    let question = render_blocks(size, &blocks, 3)?;
+    let mut answer = question.clone();
+    for &(pos, len) in &blocks {
+        answer.fill(pos..pos + len, if len == bigger { 1 } else { 2 }).ok()?;
+    }
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn construct_mark_size_two_blocks(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_mark_size_two_blocks. Gaps between blocks are at least 3, and the last block leaves at least 1 empty cell at the right border, or 3 if it has size 2. */
+    let max_count = max_blocks(size.saturating_sub(1), 1, 3);
+    if max_count < 2 {
+        return None;
+    }
+    let count = rng.gen_range(2..=max_count);
+    let budget = size - 3 * (count - 1);
+    let trailing = |len: usize| if len == 2 { 3 } else { 1 };
+    let lasts = (1..=3).filter(|&len| count - 1 + len + trailing(len) <= budget).collect::<Vec<_>>();
+    let last = lasts[rng.gen_range(0..lasts.len())];
+    let mut lengths = sample_lengths(count - 1, 1..=3, budget - last - trailing(last), rng)?;
+    lengths.push(last);
+    let blocks = place_blocks(size - trailing(last), &lengths, &vec![3; count - 1], rng)?;
+
+    let question = render_blocks(size, &blocks, 1)?;
+    let mut answer = question.clone();
+    for &(pos, block_size) in blocks.iter().filter(|(_, block_size)| *block_size == 2) {
+        if pos > 0 {
+            answer.set(pos - 1, 3).ok()?;
+        }
+        if pos + block_size < size {
+            answer.set(pos + block_size, 3).ok()?;
+        }
+    }
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+pub fn construct_color_left_half_blocks(size: usize, rng: &mut StdRng) -> Option<Example> {
+    /* Same as task_color_left_half_blocks. Blocks never end at the right border. */
+    let max_count = max_blocks(size.saturating_sub(1), 2, 1);
+    if max_count < 2 {
+        return None;
+    }
+    let count = rng.gen_range(2..=max_count);
+    let lengths = sample_lengths(count, 2..=8, size - 1 - (count - 1), rng)?;
+    let blocks = place_blocks(size - 1, &lengths, &vec![1; count - 1], rng)?;
+
+    let question = render_blocks(size, &blocks, 2)?;
+    let mut answer = question.clone();
+    for &(pos, block_size) in &blocks {
+        answer.fill(pos..pos + block_size / 2, 8).ok()?;
+    }
+    Some(Example {
+        input: question,
+        output: answer,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+
+pub fn task_mirror(example: Option<Example>) -> Option<Example> {
+    let mut example = example?;
+    example.input.reverse(..).ok()?;
+    example.output.reverse(..).ok()?;
+    Some(example)
+}
+
+pub fn task_inverse(example: Option<Example>) -> Option<Example> {
+    let mut example = example?;
+    std::mem::swap(&mut example.input, &mut example.output);
+    Some(example)
+}
+
+pub fn task_identity(example: Option<Example>) -> Option<Example> {
+    example
+}
+
+/// Rotates input and output to the right by `offset`, they should have the same size.
+pub fn task_cyclic_shift(example: Option<Example>, offset: usize) -> Option<Example> {
+    let mut example = example?;
+    example.input.rotate_right(offset);
+    example.output.rotate_right(offset);
+    Some(example)
+}
+
+/// Swaps background with `color` in input and output.
+pub fn task_swap_background(example: Option<Example>, color: u8) -> Option<Example> {
+    let example = example?;
+    let swap = |grid: &Grid| {
+        let cells = grid.iter().map(|&cell| if cell == 0 { color } else if cell == color { 0 } else { cell });
+        Grid::from_cells(cells.collect()).ok()
+    };
+    Some(Example {
+        input: swap(&example.input)?,
+        output: swap(&example.output)?,
+    })
+}
+
+/// Repeats every pixel of input and output `factor` times.
+pub fn task_upscale(example: Option<Example>, factor: usize) -> Option<Example> {
+    let example = example?;
+    let upscale = |grid: &Grid| Grid::from_cells(grid.iter().flat_map(|&cell| std::iter::repeat_n(cell, factor)).collect()).ok();
+    Some(Example {
+        input: upscale(&example.input)?,
+        output: upscale(&example.output)?,
+    })
+}
+
+/// Changes every color of the example to `permutation[color]`, see [`random_permutation`].
+pub fn task_permute_colors(example: Option<Example>, permutation: &[u8]) -> Option<Example> {
+    let example = example?;
+    let permute = |grid: &Grid| Grid::from_cells(grid.iter().map(|&cell| permutation[cell as usize]).collect()).ok();
+    Some(Example {
+        input: permute(&example.input)?,
+        output: permute(&example.output)?,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+
+// Branches of tasks. They are found from the example itself, so they work for augmented tasks too.
+
+/// Whether the block is on the left or on the right of the dot with `dot_color` in the input.
+pub fn branch_block_side_of_dot(example: &Example, dot_color: u8) -> &'static str {
+    let dot = example.input.iter().position(|&c| c == dot_color);
+    let block = example.input.iter().position(|&c| c != 0 && c != dot_color);
+    match (block, dot) {
+        (Some(block), Some(dot)) if block < dot => "left",
+        _ => "right",
+    }
+}
+
+/// Side of the block where the seed pixel is in `task_fill_from_pixel`.
+pub fn branch_fill_side(example: &Example) -> &'static str {
+    // Seed is a single pixel and the block is at least 3 pixels long
+    match example.input.objects().next() {
+        Some(first) if first.len == 1 => "left",
+        _ => "right",
+    }
+}
+
+/// Sides of the block of color 1 where `task_duplicate_block_from_seeds` has seeds.
+pub fn branch_seed_sides(example: &Example) -> &'static str {
+    let input = &example.input;
+    // Block is the longest run of color 1, seeds are single pixels
+    let Some(block) = input.objects().filter(|run| run.color.value() == 1).max_by_key(|run| run.len) else {
+        return "right";
+    };
+    let left = block.start >= 2 && input[block.start - 2] != 0;
+    let right = input.get(block.end() + 1).is_some_and(|color| !color.is_background());
+    match (left, right) {
+        (true, true) => "both",
+        (true, false) => "left",
+        _ => "right",
+    }
+}
+
+/// Side of the marker of color 5 in `task_fill_until_collision`.
+pub fn branch_marker_side(example: &Example) -> &'static str {
+    if example.input.first() == Some(&5) { "left" } else { "right" }
+}
+
+/// Whether the bigger block in `task_recolor_blocks_by_size` comes first.
+pub fn branch_bigger_block_first(example: &Example) -> &'static str {
+    if example.output.iter().find(|&&c| c != 0) == Some(&1) { "bigger_first" } else { "bigger_last" }
+}
+
+/// Size of the block in `task_copy_block_to_dots`, it's the longest object of the input, so the side of the block doesn't matter.
+pub fn branch_copied_block_size(example: &Example) -> &'static str {
+    match example.input.objects().map(|object| object.len).max() {
+        Some(3) => "block_3",
+        _ => "block_5",
+    }
+}
@@ -0,0 +1,517 @@
+// Html visualization of generated tasks
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::dataset::{matches_filter, ArcTask2D};
+use crate::field::{check_colors, colors, with_colors};
+use crate::grid::{Color, Grid};
+use crate::registry::{registry_with, Augmentation};
+use crate::{Error, Result};
+
+fn get_background_mask(grid: &Grid) -> Vec<bool> {
+    grid.colors().map(Color::is_background).collect()
+}
+
+fn count_non_background(mask: &[bool]) -> usize {
+    mask.iter().filter(|&&x| !x).count()
+}
+
+fn count_colors(grid: &Grid) -> Vec<usize> {
+    let mut res = vec![0; colors() as usize + 1];
+    for color in grid.colors() {
+        res[color.value() as usize] += 1;
+    }
+    res
+}
+
+fn empty_palette() -> Vec<bool> {
+    vec![false; colors() as usize + 1]
+}
+
+fn convert_to_palette(colors_count: &[usize]) -> Vec<bool> {
+    colors_count.iter().map(|&count| count != 0).collect()
+}
+
+fn or_palette(palette1: &[bool], palette2: &[bool]) -> Vec<bool> {
+    palette1.iter().zip(palette2.iter())
+        .map(|(&a, &b)| a || b)
+        .collect()
+}
+
+fn get_new_colors(palette_prev: &[bool], palette_new: &[bool]) -> Vec<bool> {
+    palette_prev.iter().zip(palette_new.iter())
+        .map(|(&prev, &new)| !prev && new)
+        .collect()
+}
+
+fn is_subset_palette(palette: &[bool], palette_sub: &[bool]) -> bool {
+    palette.iter().zip(palette_sub.iter()).all(|(&full, &sub)| !sub || full)
+}
+
+/// Input and output of the first test example, every grid must have exactly one row.
+pub(crate) fn test_pair(task: &ArcTask2D) -> Result<(&Grid, &Grid)> {
+    let test = task.test.first().ok_or_else(|| Error::Format("task has no test examples".to_string()))?;
+    match (&test.input[..], &test.output[..]) {
+        ([input], [output]) => Ok((input, output)),
+        (input, output) => Err(Error::Format(format!(
+            "test example has {} input rows and {} output rows, expected one row each",
+            input.len(),
+            output.len()
+        ))),
+    }
+}
+
+/// What is true for all test examples of a task.
+struct Invariants {
+    same_size: bool,
+    same_mask: bool,
+    same_count: bool,
+    same_colors: bool,
+    same_palette: bool,
+    subset_palette: bool,
+    palette_output: Vec<bool>,
+    palette_new_colors: Vec<bool>,
+}
+
+/// Mask is compared only when input and output have the same size, other invariants don't depend on positions.
+fn calc_invariants(tasks: &[ArcTask2D]) -> Result<Invariants> {
+    let mut same_size_all = true;
+    let mut same_mask_all = true;
+    let mut same_count_all = true;
+    let mut same_colors_all = true;
+    let mut same_palette_all = true;
+    let mut subset_palette_all = true;
+    let mut palette_output_all = empty_palette();
+    let mut palette_new_colors_all = empty_palette();
+    let background = Color::BLACK.value() as usize;
+
+    for task in tasks {
+        let (input, output) = test_pair(task)?;
+
+        let mask_input = get_background_mask(input);
+        let count_input = count_non_background(&mask_input);
+        let mut colors_input = count_colors(input);
+        colors_input[background] = 0;
+        let palette_input = convert_to_palette(&colors_input);
+
+        let mask_output = get_background_mask(output);
+        let count_output = count_non_background(&mask_output);
+        let mut colors_output = count_colors(output);
+        colors_output[background] = 0;
+        let palette_output = convert_to_palette(&colors_output);
+
+        let same_size = input.len() == output.len();
+        let same_mask = same_size && mask_input == mask_output;
+        let same_count = count_input == count_output;
+        let same_colors = colors_input == colors_output;
+        let same_palette = palette_input == palette_output;
+        let subset_palette = is_subset_palette(&palette_input, &palette_output);
+
+        same_size_all &= same_size;
+        same_mask_all &= same_mask;
+        same_count_all &= same_count;
+        same_colors_all &= same_colors;
+        same_palette_all &= same_palette;
+        subset_palette_all &= subset_palette;
+
+        let palette_new_colors = get_new_colors(&palette_input, &palette_output);
+
+        palette_output_all = or_palette(&palette_output_all, &palette_output);
+        palette_new_colors_all = or_palette(&palette_new_colors_all, &palette_new_colors);
+    }
+
+    Ok(Invariants {
+        same_size: same_size_all,
+        same_mask: same_mask_all,
+        same_count: same_count_all,
+        same_colors: same_colors_all,
+        same_palette: same_palette_all,
+        subset_palette: subset_palette_all,
+        palette_output: palette_output_all,
+        palette_new_colors: palette_new_colors_all,
+    })
+}
+
+fn create_palette_html(text: &str, palette: &[bool]) -> String {
+    if !palette.iter().any(|x| *x) {
+        return Default::default();
+    }
+    let mut html = format!(r#"<div>{text}:</div><div class="palette">"#);
+    for (i, &present) in palette.iter().enumerate() {
+        if present {
+            html.push_str(&format!(r#"<div class="cell color-{}"></div>"#, i));
+        }
+    }
+    html.push_str("</div>");
+    html
+}
+
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+
+pub(crate) const CELL_SIZE: u32 = 15;
+const INDEX_TAKE_JSONS: usize = 5;
+const CSS_TEMPLATE: &str = r#"
+@import url("https://fonts.googleapis.com/css2?family=Anonymous+Pro:ital,wght@0,400;0,700;1,400;1,700");
+
+@font-face {
+    font-family: 'AtariClassicChunky';
+    src: url('https://arcprize.org/media/fonts/AtariClassicChunky.eot');
+    src: url('https://arcprize.org/media/fonts/AtariClassicChunky.eot?#iefix') format('embedded-opentype'),
+        url('https://arcprize.org/media/fonts/AtariClassicChunky.woff2') format('woff2'),
+        url('https://arcprize.org/media/fonts/AtariClassicChunky.woff') format('woff'),
+        url('https://arcprize.org/media/fonts/AtariClassicChunky.svg#AtariClassicChunky') format('svg');
+    font-weight: normal;
+    font-style: normal;
+    font-display: swap;
+}
+:root {
+    --white: #EEEEEE;
+    --offwhite: #C0C0C0;
+    --black: #000000;
+    --magenta: #E53AA3;
+    --magenta-light: #ff7bcc;
+    --red: #F93C31;
+    --blue: #1E93FF;
+    --blue-light: #87D8F1;
+    --yellow: #FFDC00;
+    --orange: #FF851B;
+    --maroon: #921231;
+    --green: #4FCC30;
+    --gray: #555555;
+    --gray-light: #999999;
+}
+
+body {
+    background-color: var(--black);
+    color: var(--white);
+    font-family: 'Anonymous Pro', monospace;
+    display: flex;
+    flex-direction: column;
+    align-items: center;
+    margin: 0;
+    padding: 20px;
+}
+
+h1 {
+    font-family: 'AtariClassicChunky', monospace;
+    color: var(--magenta);
+    margin-bottom: 30px;
+}
+
+h3 {
+    word-break: break-all;
+    word-wrap: anywhere;
+    white-space: normal;
+    height: 35pt;
+    margin: 0px;
+}
+
+.task-container {
+    display: flex;
+    flex-wrap: wrap;
+    gap: 20px;
+    width: 100%;
+    max-width: 2200px;
+    justify-content: center;
+}
+
+.task {
+    flex: 0 1 auto;
+    min-width: 200px;
+    // max-width: 400px;
+
+    background-color: var(--black);
+    padding: 10px;
+
+    border: 0.5px solid var(--gray);
+}
+
+.subtask {
+    flex: 0 1 auto;
+    min-width: 200px;
+    // max-width: 400px;
+
+    background-color: var(--black);
+    padding: 10px;
+}
+
+.task-title {
+    color: var(--offwhite);
+    margin-bottom: 3px;
+    font-size: 14px;
+}
+
+.grid-container {
+    display: flex;
+    flex-direction: column;
+    gap: 3px;
+}
+
+.grid {
+    display: grid;
+    gap: 1px;
+}
+
+.cell {
+    width: 15px;
+    height: 15px;
+    border: 0.2px solid var(--gray);
+}
+
+.color-0 { background-color: var(--black); }
+.color-1 { background-color: var(--blue); }
+.color-2 { background-color: var(--red); }
+.color-3 { background-color: var(--green); }
+.color-4 { background-color: var(--yellow); }
+.color-5 { background-color: var(--gray-light); }
+.color-6 { background-color: var(--magenta); }
+.color-7 { background-color: var(--orange); }
+.color-8 { background-color: var(--blue-light); }
+.color-9 { background-color: var(--maroon); }
+{EXTRA_COLORS}
+a {
+    color: var(--blue);
+    text-decoration: none;
+}
+
+a:hover {
+    color: var(--blue-light);
+}
+
+p {
+    margin: 0px;
+}
+
+.invariants-container {
+    margin-top: 10px;
+    padding: 10px;
+    border: 1px solid #ddd;
+    border-radius: 4px;
+}
+
+.invariant {
+    display: inline-block;
+    margin: 2px 5px;
+    padding: 2px 6px;
+    background-color: #262626;
+    border-radius: 3px;
+    font-size: 0.9em;
+}
+
+.palette-container {
+    margin-top: 10px;
+}
+
+.palette {
+    display: flex;
+    gap: 2px;
+    margin: 5px 0;
+}
+"#;
+
+/// Css with classes for all colors of the current palette, colors after 9 get hues spread over the color wheel.
+pub(crate) fn css() -> String {
+    let extra_colors = (10..=colors() as u32)
+        .map(|i| format!(".color-{i} {{ background-color: hsl({}, 80%, {}%); }}\n", i * 137 % 360, if i % 2 == 0 { 45 } else { 65 }))
+        .collect::<String>();
+    CSS_TEMPLATE.replace("{EXTRA_COLORS}", &extra_colors)
+}
+
+/// Grid is drawn as one row, so input and output may have different lengths.
+fn create_grid_html(grid: &Grid) -> String {
+    let mut grid_html = format!(
+        r#"<div class="grid" style="grid-template-columns: repeat({}, {}px);">"#,
+        grid.len(), CELL_SIZE
+    );
+    
+    for color in grid.colors() {
+        grid_html.push_str(&format!(r#"<div class="cell color-{}"></div>"#, color));
+    }
+    grid_html.push_str("</div>");
+    grid_html
+}
+
+fn create_task_html(task: &ArcTask2D, task_name: &str) -> Result<String> {
+    let (input, output) = test_pair(task)?;
+    
+    Ok(format!(
+        r#"
+        <div class="subtask">
+            <div class="task-title">{}</div>
+            <div class="grid-container">
+                {}
+                {}
+            </div>
+        </div>
+        "#,
+        task_name,
+        create_grid_html(input),
+        create_grid_html(output)
+    ))
+}
+
+/// Reads a task file and checks that every task has a test pair, errors have the path of the file.
+pub(crate) fn read_tasks(path: &Path) -> Result<Vec<ArcTask2D>> {
+    let read = || -> Result<Vec<ArcTask2D>> {
+        let tasks: Vec<ArcTask2D> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        for task in &tasks {
+            test_pair(task)?;
+        }
+        Ok(tasks)
+    };
+    read().map_err(|err| Error::Task { task: path.display().to_string(), source: Box::new(err) })
+}
+
+fn generate_single_task_page(task_path: &Path, description: &str, output_dir: &Path) -> Result<PathBuf> {
+    let task_name = task_path.file_name().unwrap().to_string_lossy();
+
+    let all_files = read_tasks(task_path)?;
+    
+    let mut task_html = format!(
+        r#"
+        <!DOCTYPE html>
+        <html>
+        <head>
+            <title>ARC Task: {}</title>
+            <style>{}</style>
+        </head>
+        <body>
+            <a href="index.html">go back to all tasks</a>
+            <h1>{}</h1>
+            <p>{}</p>
+            <h3>({} files)</h3>
+            <div class="task-container">
+        "#,
+        task_name, css(), task_name, description, all_files.len()
+    );
+    
+    for (i, task_data) in all_files.iter().enumerate() {
+        task_html.push_str(&create_task_html(task_data, &i.to_string())?);
+    }
+    
+    task_html.push_str(
+        r#"
+            </div>
+        </body>
+        </html>
+        "#
+    );
+    
+    let output_path = output_dir.join(format!("{}.html", task_name));
+    let mut file = File::create(&output_path)?;
+    file.write_all(task_html.as_bytes())?;
+    
+    Ok(output_path)
+}
+
+fn generate_index_page(task_paths: &[(PathBuf, &str)], output_dir: &Path) -> Result<()> {
+    let mut index_html = format!(
+        r#"
+        <!DOCTYPE html>
+        <html>
+        <head>
+            <title>ARC Tasks Overview</title>
+            <style>{}</style>
+        </head>
+        <body>
+            <h1>ARC Tasks Overview</h1>
+            <div class="task-container">
+        "#,
+        css()
+    );
+    
+    for (path, description) in task_paths {
+        let task_dir = path.file_name().unwrap().to_string_lossy();
+        let all_files = read_tasks(path)?;
+
+        if !all_files.is_empty() {
+
+            index_html.push_str(&format!(
+                r#"<div class="task"><h3><a href="{}.html">{}</a></h3><p>{}</p>"#,
+                task_dir, task_dir.strip_suffix(".json").unwrap(), description
+            ));
+
+            let invariants = calc_invariants(&all_files)?;
+            let mut invariants_html = String::new();
+            if !invariants.same_size {
+                invariants_html.push_str("<div class='invariant'>Different size</div>");
+            }
+            if invariants.same_mask {
+                invariants_html.push_str("<div class='invariant'>Same mask</div>");
+            }
+            if invariants.same_count {
+                invariants_html.push_str("<div class='invariant'>Same count</div>");
+            }
+            if invariants.same_colors {
+                invariants_html.push_str("<div class='invariant'>Same colors</div>");
+            }
+            if invariants.same_palette {
+                invariants_html.push_str("<div class='invariant'>Same palette</div>");
+            }
+            if invariants.subset_palette {
+                invariants_html.push_str("<div class='invariant'>Subset palette</div>");
+            }
+            index_html.push_str(&format!(
+                r#"
+                <div class="invariants-container">
+                    {}
+                    <div class="palette-container">
+                        {}
+                        {}
+                    </div>
+                </div>
+                "#,
+                invariants_html,
+                create_palette_html("Output palette", &invariants.palette_output),
+                create_palette_html("New colors", &invariants.palette_new_colors)
+            ));
+            
+            let files_count = all_files.len();
+            index_html.push_str(&format!(r#"<center><p>({} files)</p></center>"#, files_count));
+            
+            for (i, json_file) in all_files.iter().enumerate().take(INDEX_TAKE_JSONS) {
+                index_html.push_str(&create_task_html(json_file, &i.to_string())?);
+            }
+            index_html.push_str("</div>");
+        }
+    }
+    
+    index_html.push_str(
+        r#"
+            </div>
+        </body>
+        </html>
+        "#
+    );
+    
+    let mut file = File::create(output_dir.join("index.html"))?;
+    file.write_all(index_html.as_bytes())?;
+    Ok(())
+}
+
+/// Renders html page of each task from `tasks_dir`, which were generated with `colors` colors, and index page with all of them.
+pub fn draw(tasks_dir: &Path, output_dir: &Path, filter: Option<&str>, colors: u8) -> Result<()> {
+    check_colors(colors)?;
+    with_colors(colors, || {
+        fs::create_dir_all(output_dir)?;
+
+        // Files of augmented tasks are drawn too, whatever augmentations were requested
+        let registry = registry_with(&Augmentation::ALL);
+        let task_paths = registry.variants().iter()
+            .filter(|variant| matches_filter(&variant.name, filter))
+            .map(|variant| (tasks_dir.join(format!("{}.json", variant.name)), variant.description))
+            .filter(|(path, _)| path.exists())
+            .collect::<Vec<_>>();
+
+        for (path, description) in &task_paths {
+            generate_single_task_page(path, description, output_dir)?;
+        }
+    
+        generate_index_page(&task_paths, output_dir)?;
+        Ok(())
+    })
+}
@@ -0,0 +1,161 @@
+// Html visualization of wrong answers of some solver
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::field::{check_colors, with_colors};
+use crate::grid::{Color, Grid};
+use crate::visualization::{css, CELL_SIZE};
+use crate::{Error, Result};
+
+#[derive(Deserialize, Serialize)]
+struct WrongPair {
+    input: Vec<i64>,
+    output: Vec<i64>,
+    task_id: String,
+    wrong_output: Vec<i64>,
+}
+
+#[derive(Serialize)]
+struct GroupedPair {
+    input: Grid,
+    output: Grid,
+    wrong_outputs: Vec<Grid>,
+}
+
+fn create_grid_html2(grid: &Grid) -> String {
+    let mut grid_html = format!(
+        r#"<div class="grid" style="grid-template-columns: repeat({}, {}px);">"#,
+        grid.len(), CELL_SIZE
+    );
+    
+    for color in grid.colors() {
+        grid_html.push_str(&format!(r#"<div class="cell color-{}"></div>"#, color));
+    }
+    grid_html.push_str("</div>");
+    grid_html
+}
+
+fn create_pair_html(input: &Grid, output: &Grid, wrong_outputs: &[Grid]) -> String {
+    let mut html = String::new();
+    
+    // Input grid
+    html.push_str("<div class='grid-container'>");
+    html.push_str("<div class='task-title'>Input:</div>");
+    html.push_str(&create_grid_html2(input));
+    
+    // Output grid
+    html.push_str("<div class='task-title'>Expected Output:</div>");
+    html.push_str(&create_grid_html2(output));
+    
+    // Wrong outputs
+    html.push_str("<div class='task-title'>Wrong Outputs:</div>");
+    for wrong_output in wrong_outputs {
+        html.push_str(&create_grid_html2(wrong_output));
+    }
+    
+    html.push_str("</div>");
+    html
+}
+
+fn create_task_html2(task_id: &str, pairs: &BTreeMap<String, GroupedPair>) -> String {
+    let mut task_html = format!(
+        r#"<div class="task">
+            <h3>{}</h3>
+            <div class="pairs-container">"#,
+        task_id
+    );
+    
+    for pair in pairs.values() {
+        task_html.push_str(&format!(
+            r#"<div class="subtask">{}</div>"#,
+            create_pair_html(&pair.input, &pair.output, &pair.wrong_outputs)
+        ));
+    }
+    
+    task_html.push_str("</div></div>");
+    task_html
+}
+
+fn generate_html(grouped_data: BTreeMap<String, BTreeMap<String, GroupedPair>>) -> String {
+    let mut html = format!(
+        r#"<!DOCTYPE html>
+        <html>
+        <head>
+            <title>Wrong Pairs Visualization</title>
+            <style>{}</style>
+        </head>
+        <body>
+            <h1>Wrong Pairs Visualization</h1>
+            <div class="task-container">"#,
+        css()
+    );
+    
+    for (task_id, pairs) in grouped_data {
+        html.push_str(&create_task_html2(&task_id, &pairs));
+    }
+    
+    html.push_str(
+        r#"</div>
+        </body>
+        </html>"#
+    );
+    
+    html
+}
+
+/// Removes two padding tokens from each side, what remains should be colors.
+fn clean_grid(tokens: &[i64]) -> Result<Grid> {
+    let cells = tokens.get(2..tokens.len().saturating_sub(2))
+        .ok_or_else(|| Error::Format(format!("grid {tokens:?} is shorter than its padding")))?;
+    cells.iter()
+        .map(|&token| u8::try_from(token).ok().and_then(Color::new)
+            .ok_or_else(|| Error::Format(format!("token {token} is not a color"))))
+        .collect::<Result<Vec<_>>>()
+        .map(Grid::from)
+}
+
+fn process_data(data: Vec<WrongPair>) -> Result<BTreeMap<String, BTreeMap<String, GroupedPair>>> {
+    let mut grouped_data: BTreeMap<String, BTreeMap<String, GroupedPair>> = Default::default();
+    
+    for pair in data {
+        let clean_input = clean_grid(&pair.input)?;
+        let clean_output = clean_grid(&pair.output)?;
+        let clean_wrong_output = clean_grid(&pair.wrong_output)?;
+        
+        let task_pairs = grouped_data.entry(pair.task_id).or_default();
+        let key = format!("{:?}-{:?}", &clean_input[..], &clean_output[..]);
+        
+        let group_pair = task_pairs.entry(key).or_insert(GroupedPair {
+            input: clean_input.clone(),
+            output: clean_output.clone(),
+            wrong_outputs: Vec::new(),
+        });
+        
+        group_pair.wrong_outputs.push(clean_wrong_output);
+    }
+    
+    Ok(grouped_data)
+}
+
+/// Renders `wrong_pairs.html` from json file with wrong outputs with `colors` colors, grouped by task and input.
+pub fn draw_wrong_pairs(input: &Path, output_dir: &Path, colors: u8) -> Result<()> {
+    check_colors(colors)?;
+    with_colors(colors, || {
+        let content = fs::read_to_string(input)?;
+        let data: Vec<WrongPair> = serde_json::from_str(&content)?;
+    
+        let grouped_data = process_data(data)?;
+        let html = generate_html(grouped_data);
+    
+        fs::create_dir_all(output_dir)?;
+    
+        let mut file = File::create(output_dir.join("wrong_pairs.html"))?;
+        file.write_all(html.as_bytes())?;
+    
+        Ok(())
+    })
+}
@@ -0,0 +1,41 @@
+// Report of `check` about ambiguous and overlapping task files
+
+use std::fs;
+
+use arc_1d::checks::check_dir;
+
+/// Task file with one episode for each `(input, output)` pair.
+fn task_file(examples: &[(&[u8], &[u8])]) -> String {
+    let episodes = examples.iter()
+        .map(|(input, output)| format!(r#"{{"train": [], "test": [{{"input": [{input:?}], "output": [{output:?}]}}]}}"#))
+        .collect::<Vec<_>>();
+    format!("[{}]", episodes.join(", "))
+}
+
+#[test]
+fn conflicts_overlaps_and_unsolved_examples_are_reported() {
+    let dir = std::env::temp_dir().join(format!("arc_1d_check_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    // Both outputs of [1, 0, 1] can't be right, and the second one is not what the solver gives
+    let right = task_file(&[(&[0, 0, 1], &[0, 0, 1]), (&[1, 1, 1], &[1, 1, 1]), (&[1, 0, 1], &[0, 1, 1]), (&[1, 0, 1], &[1, 0, 1])]);
+    let left = task_file(&[(&[0, 0, 1], &[1, 0, 0]), (&[1, 1, 1], &[1, 1, 1])]);
+    fs::write(dir.join("gravity_right.json"), right).unwrap();
+    fs::write(dir.join("gravity_left.json"), left).unwrap();
+    let report = check_dir(&dir, Some("gravity_"), 9);
+    fs::remove_dir_all(&dir).unwrap();
+    let report = report.unwrap();
+
+    assert_eq!(report.conflicts.len(), 1);
+    let (task, conflict) = &report.conflicts[0];
+    assert_eq!(task, "gravity_right");
+    assert_eq!((&conflict.input[..], &conflict.outputs[0][..], &conflict.outputs[1][..]), (&[1, 0, 1][..], &[0, 1, 1][..], &[1, 0, 1][..]));
+
+    assert_eq!(report.overlaps.len(), 1);
+    let overlap = &report.overlaps[0];
+    assert_eq!(overlap.tasks, ["gravity_right".to_string(), "gravity_left".to_string()]);
+    assert_eq!((overlap.same, overlap.different), (1, 1));
+    assert_eq!(&overlap.conflict.input[..], [0, 0, 1]);
+
+    assert_eq!(report.unsolved.iter().map(|(task, _)| task.as_str()).collect::<Vec<_>>(), ["gravity_right"]);
+    assert_eq!(&report.unsolved[0].1.input[..], [1, 0, 1]);
+}
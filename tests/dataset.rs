@@ -0,0 +1,125 @@
+// Files written by dataset generation
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use arc_1d::checks::check_dir;
+use arc_1d::dataset::{generate_all, GenerateConfig};
+use arc_1d::registry::Augmentation;
+use arc_1d::ArcTask2D;
+
+/// Empty directory in the temp dir which is unique for the test process.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("arc_1d_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn task_files_do_not_depend_on_other_tasks() {
+    let dir = temp_dir("other_tasks");
+    let alone = GenerateConfig { output_dir: dir.join("alone"), filter: Some("move_1pix_solid_right".to_string()), examples: 50, ..Default::default() };
+    let with_others = GenerateConfig {
+        output_dir: dir.join("with_others"),
+        filter: Some("move_".to_string()),
+        augmentations: vec![Augmentation::Colors],
+        ..alone.clone()
+    };
+    generate_all(&alone).unwrap();
+    let tasks = generate_all(&with_others).unwrap();
+    let alone = fs::read(alone.output_dir.join("move_1pix_solid_right.json"));
+    let with_others = fs::read(with_others.output_dir.join("move_1pix_solid_right.json"));
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(tasks.len() > 10);
+    assert_eq!(alone.unwrap(), with_others.unwrap());
+}
+
+/// Files and messages of `generate` with `jobs` threads, tasks with `bg` are ambiguous on small fields, so there are warnings.
+fn generate_with_jobs(dir: &Path, jobs: usize) -> (BTreeMap<String, Vec<u8>>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_arc_1d"))
+        .args(["generate", "--examples", "100", "--size", "3..6", "--augment", "bg", "--jobs", &jobs.to_string(), "--output-dir"])
+        .arg(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let files = fs::read_dir(dir).unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            (path.file_name().unwrap().to_string_lossy().into_owned(), fs::read(&path).unwrap())
+        })
+        .collect();
+    (files, String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn output_does_not_depend_on_number_of_threads() {
+    let dir = temp_dir("jobs");
+    let (files, messages) = generate_with_jobs(&dir.join("one"), 1);
+    let (parallel_files, parallel_messages) = generate_with_jobs(&dir.join("many"), 8);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(messages.contains("warning: task"), "{messages}");
+    assert!(files.len() > 4);
+    assert_eq!(messages, parallel_messages);
+    assert!(files == parallel_files, "files differ");
+}
+
+#[test]
+fn cells_out_of_the_palette_are_rejected_when_reading() {
+    let dir = temp_dir("palette");
+    let task = r#"[{"train": [], "test": [{"input": [[0, 10, 0]], "output": [[0, 0, 10]]}]}]"#;
+    fs::write(dir.join("gravity_right.json"), task).unwrap();
+    let small = check_dir(&dir, None, 9);
+    let big = check_dir(&dir, None, 10);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let err = small.expect_err("color 10 is out of 9 colors").to_string();
+    assert!(err.contains("gravity_right.json") && err.contains("color 10 is not in the palette 0..=9"), "{err}");
+    assert!(big.unwrap().unsolved.is_empty());
+}
+
+#[test]
+fn episodes_have_train_pairs_from_other_examples() {
+    let dir = temp_dir("episodes");
+    let config = GenerateConfig { output_dir: dir.clone(), filter: Some("gravity_right".to_string()), examples: 20, train_pairs: 3, ..Default::default() };
+    generate_all(&config).unwrap();
+    let tasks: Vec<ArcTask2D> = serde_json::from_str(&fs::read_to_string(dir.join("gravity_right.json")).unwrap()).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(tasks.len(), 20);
+    for task in &tasks {
+        assert_eq!(task.train.len(), 3);
+        assert!(task.train.iter().all(|train| train.input != task.test[0].input));
+    }
+}
+
+#[test]
+fn permuted_tasks_keep_their_fixed_colors() {
+    let dir = temp_dir("perm");
+    let config = GenerateConfig {
+        output_dir: dir.clone(),
+        filter: Some("block_touch_dot_1_pix_perm".to_string()),
+        examples: 30,
+        augmentations: vec![Augmentation::Colors],
+        ..Default::default()
+    };
+    generate_all(&config).unwrap();
+    let tasks: Vec<ArcTask2D> = serde_json::from_str(&fs::read_to_string(dir.join("block_touch_dot_1_pix_perm.json")).unwrap()).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(tasks.len(), 30);
+    let mut moved = 0;
+    for task in &tasks {
+        let permutation = task.metadata.as_ref().and_then(|metadata| metadata.permutation.as_ref()).expect("episode has its permutation");
+        assert_eq!((permutation[0], permutation[2]), (0, 2));
+        // Dot of the task has fixed color 2, the block has any other color
+        let input = &task.test[0].input[0];
+        assert_eq!(input.iter().filter(|&&cell| cell == 2).count(), 1, "{:?}", &input[..]);
+        moved += usize::from(permutation.iter().enumerate().any(|(color, &new)| color as u8 != new));
+    }
+    assert!(moved > 0);
+}
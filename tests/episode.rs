@@ -0,0 +1,30 @@
+// Choice of train pairs for episodes
+
+use arc_1d::episode::choose_train;
+use arc_1d::{Example, Grid};
+
+fn example(input: &[u8], output: &[u8]) -> Example {
+    Example { input: Grid::from_cells(input.to_vec()).unwrap(), output: Grid::from_cells(output.to_vec()).unwrap() }
+}
+
+#[test]
+fn train_pairs_explained_by_identity_are_avoided() {
+    // Pixels fall to the right, settled fields are explained by identity too
+    let settled = [&[0, 1][..], &[0, 0, 1], &[0, 1, 1], &[0, 0, 0, 1], &[0, 0, 1, 1]];
+    let falling = [(&[1, 0, 1, 0][..], &[0, 0, 1, 1][..]), (&[1, 1, 0, 0], &[0, 0, 1, 1]), (&[1, 0, 0, 1, 0], &[0, 0, 0, 1, 1]), (&[1, 0, 1, 0, 0], &[0, 0, 0, 1, 1]), (&[1, 1, 0, 1, 0], &[0, 0, 1, 1, 1])];
+    let mut examples = vec![example(&[1, 0], &[0, 1])];
+    examples.extend(settled.iter().map(|&input| example(input, input)));
+    let only_settled = examples.clone();
+    examples.extend(falling.iter().map(|&(input, output)| example(input, output)));
+
+    for seed in 0..20 {
+        let (train, metadata) = choose_train(&examples, 0, 2, None, seed).unwrap();
+        assert!(!metadata.confounded, "seed {seed}: {train:?}");
+        assert!(train.iter().any(|&i| i > settled.len()), "seed {seed}: {train:?}");
+        assert!(!train.contains(&0));
+
+        // Nothing better can be chosen, so it's marked in the metadata
+        let (_, metadata) = choose_train(&only_settled, 0, 2, None, seed).unwrap();
+        assert!(metadata.confounded);
+    }
+}
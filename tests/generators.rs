@@ -0,0 +1,274 @@
+// Properties of all generators for all sizes, palettes and seeds
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use arc_1d::field::{colors, with_colors, COLORS, MAX_COLORS};
+use arc_1d::dataset::GenerateConfig;
+use arc_1d::registry::{registry, registry_with, Augmentation, Sampling, TaskVariant};
+use arc_1d::splits::generate_splits;
+use arc_1d::{ArcTask2D, Example};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const MAX_SIZE: usize = 64;
+
+fn generate(variant: &TaskVariant, sampling: Sampling, size: usize, seed: u64) -> Option<Example> {
+    variant.sample(sampling, size, &mut StdRng::seed_from_u64(seed))
+}
+
+/// Result of generating one example, see [`check`].
+enum Checked {
+    Generated,
+    Rejected,
+    Problem(String),
+}
+
+/// Generates the example of `variant` for `size` and finds what's wrong with it.
+///
+/// Constructive generators must not reject anything for sizes from `min_size`.
+fn check(variant: &TaskVariant, sampling: Sampling, size: usize, seed: u64) -> Checked {
+    let Ok(example) = catch_unwind(AssertUnwindSafe(|| generate(variant, sampling, size, seed))) else {
+        return Checked::Problem("panicked".to_string());
+    };
+    let Some(example) = example else {
+        let constructive = sampling == Sampling::Constructive && variant.is_constructive();
+        return if constructive && size >= variant.min_size { Checked::Problem("constructive generator rejected".to_string()) } else { Checked::Rejected };
+    };
+    match problem(variant, sampling, size, seed, &example) {
+        Some(problem) => Checked::Problem(problem),
+        None => Checked::Generated,
+    }
+}
+
+/// Finds what's wrong with the example of `variant` generated for `size`, `None` if nothing.
+fn problem(variant: &TaskVariant, sampling: Sampling, size: usize, seed: u64, example: &Example) -> Option<String> {
+    if generate(variant, sampling, size, seed).as_ref() != Some(example) {
+        return Some("different example for the same seed".to_string());
+    }
+
+    let scale = if variant.augmentations.contains(&Augmentation::Upscale) { 2 } else { 1 };
+    // Inverse task gets the input of the original task as its output
+    let original_input = if variant.augmentations.contains(&Augmentation::Inverse) { &example.output } else { &example.input };
+    if original_input.len() != size * scale {
+        return Some(format!("input has size {}", original_input.len()));
+    }
+    if variant.same_size && example.input.len() != example.output.len() {
+        return Some(format!("output has size {}, input has size {}", example.output.len(), example.input.len()));
+    }
+    if let Some(&color) = example.input.iter().chain(example.output.iter()).find(|&&color| color > colors()) {
+        return Some(format!("color {color} is out of palette"));
+    }
+    if !variant.verify(example) {
+        return Some(format!("reference solver doesn't explain {example:?}"));
+    }
+    None
+}
+
+/// Checks every size from 1 to [`MAX_SIZE`] for every variant, sizes below `min_size` must not panic too.
+fn check_all<'a>(variants: impl IntoIterator<Item = &'a TaskVariant>, sampling: Sampling, colors: u8, seeds: u64) {
+    let mut problems = vec![];
+    with_colors(colors, || {
+        for variant in variants.into_iter().filter(|variant| variant.min_colors <= colors) {
+            let mut generated = 0;
+            for size in 1..=MAX_SIZE {
+                for seed in 0..seeds {
+                    match check(variant, sampling, size, seed) {
+                        Checked::Generated => generated += 1,
+                        Checked::Rejected => {}
+                        Checked::Problem(problem) => problems.push(format!("{} with size {size}, seed {seed} and {colors} colors: {problem}", variant.name)),
+                    }
+                }
+            }
+            if generated == 0 {
+                problems.push(format!("{} never generates anything with {colors} colors", variant.name));
+            }
+        }
+    });
+    assert!(problems.is_empty(), "{} problems:\n{}", problems.len(), problems.join("\n"));
+}
+
+#[test]
+fn all_tasks_for_all_sizes() {
+    check_all(registry().variants(), Sampling::Rejection, COLORS, 32);
+}
+
+#[test]
+fn all_augmentations_for_all_sizes() {
+    check_all(registry_with(&Augmentation::ALL).variants(), Sampling::Rejection, COLORS, 4);
+}
+
+#[test]
+fn small_and_big_palettes() {
+    for colors in [1, 2, 3, 5, 8, 16, MAX_COLORS] {
+        check_all(registry().variants(), Sampling::Rejection, colors, 8);
+    }
+}
+
+#[test]
+fn constructive_generators_for_all_sizes() {
+    let registry = registry_with(&Augmentation::ALL);
+    check_all(registry.variants().iter().filter(|variant| variant.is_constructive()), Sampling::Constructive, COLORS, 16);
+}
+
+#[test]
+fn enumeration_is_exactly_what_generators_produce() {
+    // Other augmentations have only one outcome
+    let registry = registry_with(&[Augmentation::Shift, Augmentation::Background]);
+    let mut problems = vec![];
+    // Small palette and sizes, so random sampling covers every example
+    with_colors(2, || {
+        for variant in registry.variants().iter().filter(|variant| variant.is_enumerable() && variant.min_colors <= 2) {
+            for size in variant.min_size..=4 {
+                let enumerated = variant.enumerate(size).unwrap().collect::<HashSet<_>>();
+                let generated = (0..4_000).filter_map(|seed| generate(variant, Sampling::Rejection, size, seed)).collect::<HashSet<_>>();
+                if let Some(example) = enumerated.iter().find(|example| !variant.verify(example)) {
+                    problems.push(format!("{} with size {size}: reference solver doesn't explain enumerated {example:?}", variant.name));
+                }
+                if let Some(example) = generated.difference(&enumerated).next() {
+                    problems.push(format!("{} with size {size}: {example:?} is generated, but not enumerated", variant.name));
+                }
+                if let Some(example) = enumerated.difference(&generated).next() {
+                    problems.push(format!("{} with size {size}: {example:?} is enumerated, but never generated", variant.name));
+                }
+            }
+        }
+    });
+    assert!(problems.is_empty(), "{} problems:\n{}", problems.len(), problems.join("\n"));
+}
+
+#[test]
+fn held_out_attributes_are_only_in_held_out_splits() {
+    let config = GenerateConfig { holdouts: vec!["color=7".parse().unwrap(), "start=2/3..1".parse().unwrap()], ..Default::default() };
+    let held_out = GenerateConfig { held_out: true, ..config.clone() };
+    let mut problems = vec![];
+    for variant in registry_with(&Augmentation::ALL).variants() {
+        for size in [5, 12, 29] {
+            for seed in 0..8 {
+                let Some(example) = generate(variant, Sampling::Rejection, size, seed) else {
+                    continue;
+                };
+                let has_color = example.input.iter().chain(example.output.iter()).any(|&color| color == 7);
+                let starts_late = example.input.objects().any(|object| 3 * object.start >= 2 * example.input.len());
+                if config.keeps(variant, &example) && (has_color || starts_late) {
+                    problems.push(format!("{}: {example:?} is not held out", variant.name));
+                }
+                if held_out.keeps(variant, &example) && !has_color && !starts_late {
+                    problems.push(format!("{}: {example:?} is in the held out split without held out attributes", variant.name));
+                }
+            }
+        }
+    }
+    assert!(problems.is_empty(), "{} problems:\n{}", problems.len(), problems.join("\n"));
+}
+
+#[test]
+fn held_out_split_is_filled_with_held_out_examples() {
+    let dir = std::env::temp_dir().join(format!("arc_1d_held_out_{}", std::process::id()));
+    let config = GenerateConfig {
+        output_dir: dir.clone(),
+        filter: Some("two_points_and_fill".to_string()),
+        examples: 30,
+        holdouts: vec!["color=7".parse().unwrap()],
+        ..Default::default()
+    };
+    let manifest = generate_splits(&config, &["ood=5..=12:held-out".parse().unwrap()]);
+    let tasks = manifest.as_ref().map(|manifest| {
+        manifest.splits[0].tasks.iter()
+            .map(|task| {
+                let json = fs::read_to_string(dir.join("ood").join(format!("{}.json", task.task))).unwrap();
+                (task.task.clone(), serde_json::from_str::<Vec<ArcTask2D>>(&json).unwrap())
+            })
+            .collect::<Vec<_>>()
+    });
+    fs::remove_dir_all(&dir).unwrap();
+
+    let tasks = tasks.unwrap();
+    assert_eq!(tasks.len(), 2);
+    for (task, episodes) in tasks {
+        assert_eq!(episodes.len(), 30, "{task}");
+        for episode in episodes {
+            let example = &episode.test[0];
+            assert!(example.input[0].iter().chain(example.output[0].iter()).any(|&color| color == 7), "{task}: {example:?}");
+        }
+    }
+}
+
+#[test]
+fn permutation_keeps_held_out_colors_in_their_splits() {
+    let dir = std::env::temp_dir().join(format!("arc_1d_held_out_perm_{}", std::process::id()));
+    let config = GenerateConfig {
+        output_dir: dir.clone(),
+        filter: Some("move_1pix_solid_right_perm".to_string()),
+        examples: 30,
+        train_pairs: 2,
+        augmentations: vec![Augmentation::Colors],
+        holdouts: vec!["color=7".parse().unwrap()],
+        ..Default::default()
+    };
+    let manifest = generate_splits(&config, &["train=5..=12".parse().unwrap(), "ood=13..=20:held-out".parse().unwrap()]);
+    let splits = manifest.as_ref().map(|manifest| {
+        manifest.splits.iter()
+            .map(|split| {
+                let names = split.tasks.iter().map(|task| task.task.clone()).collect::<Vec<_>>();
+                let json = fs::read_to_string(dir.join(&split.name).join("move_1pix_solid_right_perm.json")).unwrap();
+                (split.held_out, names, serde_json::from_str::<Vec<ArcTask2D>>(&json).unwrap())
+            })
+            .collect::<Vec<_>>()
+    });
+    fs::remove_dir_all(&dir).unwrap();
+
+    for (held_out, names, episodes) in splits.unwrap() {
+        assert_eq!(names, ["move_1pix_solid_right_perm"]);
+        assert_eq!(episodes.len(), 30);
+        for episode in episodes {
+            let has_color = |examples: &[arc_1d::Example2D]| examples.iter().any(|example| example.input[0].iter().chain(example.output[0].iter()).any(|&color| color == 7));
+            if held_out {
+                assert!(has_color(&episode.test), "{episode:?}");
+            } else {
+                assert!(!has_color(&episode.train) && !has_color(&episode.test), "{episode:?}");
+            }
+        }
+    }
+}
+
+/// Values of one feature of input layouts, like lengths of objects, which appear in some example.
+type Support = BTreeMap<&'static str, BTreeSet<usize>>;
+
+/// Numbers of objects, their lengths, gaps between them and empty cells at both borders in inputs of `examples`.
+fn layout_support(examples: &[Example]) -> Support {
+    let mut support = Support::new();
+    for example in examples {
+        let objects = example.input.objects().collect::<Vec<_>>();
+        support.entry("count").or_default().insert(objects.len());
+        for object in &objects {
+            support.entry("length").or_default().insert(object.len);
+        }
+        for pair in objects.windows(2) {
+            support.entry("gap").or_default().insert(pair[1].start - pair[0].end());
+        }
+        if let (Some(first), Some(last)) = (objects.first(), objects.last()) {
+            support.entry("left border").or_default().insert(first.start);
+            support.entry("right border").or_default().insert(example.input.len() - last.end());
+        }
+    }
+    support
+}
+
+#[test]
+fn constructive_generators_have_the_support_of_rejection() {
+    let mut problems = vec![];
+    for variant in registry().variants().iter().filter(|variant| variant.is_constructive()) {
+        // Small sizes, so rejection sampling covers every layout
+        for size in variant.min_size..=variant.min_size + 3 {
+            let sample = |sampling| (0..10_000).filter_map(|seed| generate(variant, sampling, size, seed)).collect::<Vec<_>>();
+            let rejection = layout_support(&sample(Sampling::Rejection));
+            let constructive = layout_support(&sample(Sampling::Constructive));
+            if rejection != constructive {
+                problems.push(format!("{} with size {size}: rejection has {rejection:?}, constructive has {constructive:?}", variant.name));
+            }
+        }
+    }
+    assert!(problems.is_empty(), "{} problems:\n{}", problems.len(), problems.join("\n"));
+}
@@ -0,0 +1,50 @@
+// Grids check their cells on construction and on every write
+
+use std::fs;
+
+use arc_1d::field::with_colors;
+use arc_1d::visualization::draw;
+use arc_1d::{Error, Grid};
+
+#[test]
+fn cells_are_checked_on_construction() {
+    with_colors(9, || {
+        assert_eq!(&Grid::from_cells(vec![0, 9, 1]).unwrap()[..], [0, 9, 1]);
+        assert!(matches!(Grid::from_cells(vec![0, 10]), Err(Error::InvalidColor { color: 10, colors: 9 })));
+        assert!(Grid::try_from(vec![3, 12]).is_err());
+        assert!(with_colors(12, || Grid::try_from(vec![3, 12]).is_ok()));
+    });
+}
+
+#[test]
+fn failed_writes_change_nothing() {
+    with_colors(9, || {
+        let mut grid = Grid::new(4);
+        grid.set(1, 3).unwrap();
+        grid.fill(2..=3, 5).unwrap();
+        grid.write_block(0, &[7]).unwrap();
+        assert_eq!(&grid[..], [7, 3, 5, 5]);
+
+        assert!(matches!(grid.set(4, 1), Err(Error::OutOfBounds { .. })));
+        assert!(matches!(grid.set(0, 10), Err(Error::InvalidColor { color: 10, .. })));
+        assert!(grid.fill(3..5, 1).is_err());
+        assert!(grid.fill(.., 10).is_err());
+        assert!(grid.write_block(2, &[1, 1, 1]).is_err());
+        assert!(grid.write_block(0, &[1, 10]).is_err());
+        assert!(grid.reverse(2..6).is_err());
+        assert_eq!(&grid[..], [7, 3, 5, 5]);
+    });
+}
+
+#[test]
+fn test_pairs_with_several_rows_are_rejected() {
+    let dir = std::env::temp_dir().join(format!("arc_1d_rows_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let task = r#"[{"train": [], "test": [{"input": [[0, 1, 0], [1, 0, 0]], "output": [[0, 0, 1]]}]}]"#;
+    fs::write(dir.join("gravity_right.json"), task).unwrap();
+    let result = draw(&dir, &dir.join("html"), None, 9);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let err = result.expect_err("input has two rows").to_string();
+    assert!(err.contains("2 input rows and 1 output rows"), "{err}");
+}
@@ -0,0 +1,73 @@
+// Parity of generated examples with 1D-ARC, when they are saved in its layout
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use arc_1d::dataset::{generate_all, save_json_to_file, GenerateConfig};
+use arc_1d::field::COLORS;
+use arc_1d::original_1d_arc::{export, parity};
+use arc_1d::{generate, ArcTask2D};
+
+#[test]
+fn generated_problems_reproduce_their_category() {
+    let dir = std::env::temp_dir().join(format!("arc_1d_parity_{}", std::process::id()));
+    for (category, task) in [("1d_move_dp", "block_touch_dot"), ("1d_recolor_cmp", "paint_biggest_block")] {
+        fs::create_dir_all(dir.join(category)).unwrap();
+        let examples = generate(task, 40, 42).unwrap();
+        for (i, chunk) in examples.chunks(4).enumerate() {
+            let problem = ArcTask2D {
+                train: chunk[..3].iter().cloned().map(Into::into).collect(),
+                test: vec![chunk[3].clone().into()],
+                metadata: None,
+            };
+            save_json_to_file(&problem, &dir.join(category).join(format!("{category}_{i}.json"))).unwrap();
+        }
+    }
+    let report = parity(&dir, 1000, 42, COLORS);
+    fs::remove_dir_all(&dir).unwrap();
+    let report = report.unwrap();
+
+    assert_eq!(report.categories.len(), 2);
+    for category in &report.categories {
+        assert_eq!(category.examples, 40);
+        assert_eq!(category.mapped.iter().map(|(_, explained)| *explained).collect::<Vec<_>>(), [40], "{}", category.category);
+        assert!(category.gaps.is_empty(), "{}: {:?}", category.category, category.gaps);
+    }
+    assert!(report.unimplemented.is_empty());
+    assert!(report.missing.contains(&"1d_fill".to_string()));
+}
+
+#[test]
+fn exported_tasks_have_the_layout_of_1d_arc() {
+    let dir = std::env::temp_dir().join(format!("arc_1d_export_{}", std::process::id()));
+    let tasks_dir = dir.join("tasks");
+    let config = GenerateConfig { output_dir: tasks_dir.clone(), filter: Some("block_touch_dot".to_string()), examples: 10, train_pairs: 3, ..Default::default() };
+    generate_all(&config).unwrap();
+    // Episodes without train pairs can't be problems of 1D-ARC
+    generate_all(&GenerateConfig { filter: Some("gravity_right".to_string()), train_pairs: 0, ..config }).unwrap();
+    let names = export(&tasks_dir, &dir.join("export"), None, Some(4), COLORS);
+    let saved_names: BTreeMap<String, String> = serde_json::from_str(&fs::read_to_string(dir.join("export/names.json")).unwrap()).unwrap();
+    let folder = |name: &str| {
+        let mut files = fs::read_dir(dir.join("export").join(name)).unwrap().map(|entry| entry.unwrap().file_name().into_string().unwrap()).collect::<Vec<_>>();
+        files.sort();
+        files
+    };
+    let move_dp = folder("1d_move_dp");
+    let own_folder = folder("block_touch_dot_1_pix");
+    let problem: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join("export/1d_move_2p_dp/1d_move_2p_dp_0.json")).unwrap()).unwrap();
+    let gravity_exported = dir.join("export/gravity_right").exists();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let names = names.unwrap();
+    assert_eq!(names, saved_names);
+    assert_eq!(names.len(), 5);
+    assert_eq!(names["block_touch_dot"], "1d_move_dp");
+    assert_eq!(names["block_touch_dot_2_pix"], "1d_move_2p_dp");
+    assert_eq!(names["block_touch_dot_1_pix"], "block_touch_dot_1_pix");
+    assert!(!gravity_exported && !names.contains_key("gravity_right"));
+    assert_eq!(move_dp, ["1d_move_dp_0.json", "1d_move_dp_1.json", "1d_move_dp_2.json", "1d_move_dp_3.json"]);
+    assert_eq!(own_folder.len(), 4);
+    let keys = problem.as_object().unwrap().keys().collect::<Vec<_>>();
+    assert_eq!(keys, ["test", "train"]);
+    assert_eq!(problem["train"].as_array().unwrap().len(), 3);
+}
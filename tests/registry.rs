@@ -0,0 +1,84 @@
+// Registration of task definitions and what variants get from them
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use arc_1d::registry::{no_params, param_grid, Augmentation, Sampling, TaskDef};
+use arc_1d::tasks::task_gravity;
+use arc_1d::{registry_with, Error, Registry, TaskVariant};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn def(name: &'static str, params: Vec<arc_1d::registry::Params>) -> TaskDef {
+    TaskDef {
+        name,
+        description: "All pixels fall to the right.",
+        params,
+        min_size: |_| 1,
+        same_size: true,
+        min_colors: 1,
+        fixed_colors: &[],
+        augmentations: &[],
+        symmetries: &[],
+        branches: None,
+        generate: |size, _, rng| task_gravity(size, rng),
+        construct: None,
+        enumerate: None,
+        solve: |_, _| None,
+    }
+}
+
+#[test]
+fn tasks_with_the_same_expanded_name_are_rejected() {
+    let mut registry = Registry::with_augmentations(&[]);
+    registry.register(def("gravity_{pixels}", param_grid("pixels", 1..=2))).unwrap();
+    let result = registry.register(def("gravity_2", no_params()));
+    assert!(matches!(&result, Err(Error::Registry(message)) if message.contains("`gravity_2`")), "{result:?}");
+    assert_eq!(registry.variants().len(), 2);
+}
+
+#[test]
+fn mirrored_copies_of_blocks_have_both_branches() {
+    let registry = registry_with(&[Augmentation::Mirror]);
+    for task in ["copy_block_to_dots", "copy_block_to_dots_colors"] {
+        let original = registry.get(task).unwrap();
+        let mirrored = registry.get(&format!("{task}_mirror")).unwrap();
+        let classify = mirrored.branches.expect("mirroring keeps branches").classify;
+        let mut branches = BTreeSet::new();
+        for seed in 0..100 {
+            let sample = |variant: &TaskVariant| variant.sample(Sampling::Rejection, 20, &mut StdRng::seed_from_u64(seed));
+            let (Some(example), Some(mirrored_example)) = (sample(original), sample(mirrored)) else {
+                continue;
+            };
+            assert_eq!(classify(&mirrored_example), classify(&example), "{task}: {mirrored_example:?}");
+            branches.insert(classify(&mirrored_example));
+        }
+        assert_eq!(branches.into_iter().collect::<Vec<_>>(), ["block_3", "block_5"], "{task}");
+    }
+}
+
+#[test]
+fn augmentations_which_keep_branches_keep_examples_of_a_branch_together() {
+    let registry = registry_with(&Augmentation::ALL);
+    let mut checked = 0;
+    for variant in registry.variants().iter().filter(|variant| variant.branches.is_some() && !variant.augmentations.is_empty()) {
+        let original = registry.variants().iter()
+            .find(|other| other.description == variant.description && other.params == variant.params && other.augmentations.is_empty())
+            .unwrap();
+        let classify = variant.branches.unwrap().classify;
+        let classify_original = original.branches.expect("augmented task has branches of the original").classify;
+        // Mirroring may swap branches like `left` and `right`, but examples of one branch must stay in one branch
+        let mut renamed = BTreeMap::new();
+        for seed in 0..200 {
+            let sample = |variant: &TaskVariant| variant.sample(Sampling::Rejection, 20, &mut StdRng::seed_from_u64(seed));
+            let (Some(example), Some(augmented)) = (sample(original), sample(variant)) else {
+                continue;
+            };
+            let branch = *renamed.entry(classify_original(&example)).or_insert(classify(&augmented));
+            assert_eq!(classify(&augmented), branch, "{}: {augmented:?}", variant.name);
+        }
+        let branches = renamed.values().collect::<BTreeSet<_>>();
+        assert_eq!(branches.len(), variant.branches.unwrap().names.len(), "{}: {renamed:?}", variant.name);
+        checked += 1;
+    }
+    assert!(checked > 0);
+}
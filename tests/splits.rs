@@ -0,0 +1,69 @@
+// Dataset splits and their manifest
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use arc_1d::dataset::GenerateConfig;
+use arc_1d::splits::{generate_splits, split_seed, Split};
+use arc_1d::{ArcTask2D, Error};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("arc_1d_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn splits(splits: &[&str]) -> Vec<Split> {
+    splits.iter().map(|split| split.parse().unwrap()).collect()
+}
+
+#[test]
+fn overlapping_splits_are_rejected() {
+    let dir = temp_dir("overlapping_splits");
+    let config = GenerateConfig { output_dir: dir.clone(), filter: Some("gravity_right".to_string()), examples: 10, ..Default::default() };
+    for given in [&["train=5..=12", "test=12..=20"][..], &["train=5..=8", "train=10..=12"]] {
+        let result = generate_splits(&config, &splits(given));
+        assert!(matches!(result, Err(Error::Split(_))), "{given:?}: {result:?}");
+    }
+    assert!(!dir.exists());
+}
+
+#[test]
+fn manifest_describes_every_split() {
+    let dir = temp_dir("manifest");
+    let config = GenerateConfig { output_dir: dir.clone(), filter: Some("gravity_right".to_string()), examples: 10, train_pairs: 2, ..Default::default() };
+    generate_splits(&config, &splits(&["train=5..=8", "test=20..=24"])).unwrap();
+    let manifest: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join("manifest.json")).unwrap()).unwrap();
+    let test: Vec<ArcTask2D> = serde_json::from_str(&fs::read_to_string(dir.join("test/gravity_right.json")).unwrap()).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!((&manifest["seed"], &manifest["examples"], &manifest["train_pairs"], &manifest["colors"]), (&42.into(), &10.into(), &2.into(), &9.into()));
+    let splits = manifest["splits"].as_array().unwrap();
+    assert_eq!(splits.len(), 2);
+    for (split, name, min, max) in [(&splits[0], "train", 5, 8), (&splits[1], "test", 20, 24)] {
+        assert_eq!(split["name"], name);
+        assert_eq!((&split["sizes"]["min"], &split["sizes"]["max"]), (&min.into(), &max.into()));
+        assert_eq!(split["seed"], split_seed(42, name));
+        let tasks = split["tasks"].as_array().unwrap();
+        assert!(tasks.iter().any(|task| task["task"] == "gravity_right" && task["count"] == 10), "{tasks:?}");
+    }
+    assert!(test.iter().all(|task| (20..=24).contains(&task.test[0].input[0].len())));
+}
+
+#[test]
+fn task_sizes_are_rejected_with_splits() {
+    let dir = temp_dir("task_sizes");
+    let config = GenerateConfig { output_dir: dir.clone(), task_sizes: vec![("gravity".to_string(), "10".parse().unwrap())], ..Default::default() };
+    let result = generate_splits(&config, &splits(&["train=5..=8"]));
+    assert!(matches!(result, Err(Error::Split(_))), "{result:?}");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arc_1d"))
+        .args(["generate", "--split", "train=5..=8", "--task-size", "gravity=10", "--output-dir"])
+        .arg(&dir)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+    assert!(!dir.exists());
+}
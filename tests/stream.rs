@@ -0,0 +1,41 @@
+// Json lines of the example stream
+
+use std::io::{self, Write};
+
+use arc_1d::registry;
+use arc_1d::stream::{write_stream, ExampleStream, StreamConfig};
+
+/// Reader which takes `lines` writes and then closes the pipe.
+struct Pipe {
+    writes: Vec<Vec<u8>>,
+    lines: usize,
+}
+
+impl Write for Pipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.writes.len() == self.lines {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        self.writes.push(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn endless_stream_is_written_line_by_line_until_the_pipe_is_closed() {
+    let registry = registry();
+    let stream = ExampleStream::new(&registry, &StreamConfig::default()).unwrap();
+    let mut pipe = Pipe { writes: vec![], lines: 3 };
+    write_stream(stream, &mut pipe).unwrap();
+
+    assert_eq!(pipe.writes.len(), 3);
+    for write in &pipe.writes {
+        let line = std::str::from_utf8(write).unwrap();
+        assert!(line.ends_with('\n') && line.matches('\n').count() == 1, "{line}");
+        assert!(serde_json::from_str::<serde_json::Value>(line).is_ok(), "{line}");
+    }
+}